@@ -1,9 +1,12 @@
+use crate::case::RenameRule;
 use crate::ctx::Ctx;
 use crate::respan::respan;
 use crate::symbol::*;
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use syn::{Attribute, Meta, NestedMeta};
 
+#[derive(Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum From {
     Default,
     FromStr,
@@ -12,21 +15,44 @@ pub enum From {
 }
 
 /// container attributes
+#[derive(Clone)]
 pub struct Container {
     pub tag_name: Option<String>,
+    pub alias: Vec<String>,
     pub from: From,
     pub allow_unknown_children: bool,
     pub allow_unknown_attributes: bool,
     pub allow_unknown_text: bool,
+    pub finish: Option<syn::ExprPath>,
+    pub tag_attribute: Option<syn::LitStr>,
+    pub case_insensitive: bool,
+    pub untagged: bool,
+    pub text: bool,
+    pub xsi_type: bool,
+    pub bound: Option<Vec<syn::WherePredicate>>,
+    pub rename_all: Option<RenameRule>,
+    pub rename_all_attributes: Option<RenameRule>,
+    pub ordered: bool,
 }
 
 impl Container {
     pub fn from_attrs(ctx: &Ctx, attrs: &Vec<Attribute>) -> Self {
         let mut tag_name = None;
+        let mut alias = Vec::new();
         let mut from = None;
         let mut allow_unknown_children = false;
         let mut allow_unknown_attributes = false;
         let mut allow_unknown_text = false;
+        let mut finish = None;
+        let mut tag_attribute = None;
+        let mut case_insensitive = false;
+        let mut untagged = false;
+        let mut text = None;
+        let mut ordered = false;
+        let mut xsi_type = None;
+        let mut bound = None;
+        let mut rename_all = None;
+        let mut rename_all_attributes = None;
 
         for attr in attrs {
             if attr.path != ASYNC_XML {
@@ -44,6 +70,12 @@ impl Container {
                                     }
                                 }
                             }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == ALIAS => {
+                                let str = get_lit_str(ctx, &m.lit);
+                                if let Ok(str) = str {
+                                    alias.push(str.value());
+                                }
+                            }
                             NestedMeta::Meta(Meta::Path(m)) if m == FROM_STR => {
                                 if from.replace(From::FromStr).is_some() {
                                     ctx.error_spanned_by(m, "from already specified");
@@ -79,6 +111,69 @@ impl Container {
                                 allow_unknown_attributes = true;
                                 allow_unknown_text = true;
                             }
+                            NestedMeta::Meta(Meta::Path(m)) if m == CASE_INSENSITIVE => {
+                                case_insensitive = true;
+                            }
+                            NestedMeta::Meta(Meta::Path(m)) if m == UNTAGGED => {
+                                untagged = true;
+                            }
+                            NestedMeta::Meta(Meta::Path(m)) if m == TEXT => {
+                                text = Some(m);
+                            }
+                            NestedMeta::Meta(Meta::Path(m)) if m == ORDERED => {
+                                ordered = true;
+                            }
+                            NestedMeta::Meta(Meta::Path(m)) if m == XSI_TYPE => {
+                                xsi_type = Some(m);
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == TAG_ATTRIBUTE => {
+                                let str = get_lit_str(ctx, &m.lit);
+                                if let Ok(str) = str {
+                                    if tag_attribute.replace(str.clone()).is_some() {
+                                        ctx.error_spanned_by(
+                                            m,
+                                            "tag attribute given multiple times",
+                                        );
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == RENAME_ALL => {
+                                let rule = get_lit_str_as_rename_rule(ctx, &m.lit);
+                                if let Ok(rule) = rule {
+                                    if rename_all.replace(rule).is_some() {
+                                        ctx.error_spanned_by(m, "rename_all given multiple times");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m))
+                                if m.path == RENAME_ALL_ATTRIBUTES =>
+                            {
+                                let rule = get_lit_str_as_rename_rule(ctx, &m.lit);
+                                if let Ok(rule) = rule {
+                                    if rename_all_attributes.replace(rule).is_some() {
+                                        ctx.error_spanned_by(
+                                            m,
+                                            "rename_all_attributes given multiple times",
+                                        );
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == BOUND => {
+                                let predicates = get_lit_str_as_where_predicates(ctx, &m.lit);
+                                if let Ok(predicates) = predicates {
+                                    if bound.replace(predicates).is_some() {
+                                        ctx.error_spanned_by(m, "bound already specified");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == FINISH => {
+                                let path = get_lit_str_as_expr_path(ctx, &m.lit);
+                                if let Ok(path) = path {
+                                    if finish.replace(path).is_some() {
+                                        ctx.error_spanned_by(m, "finish already specified");
+                                    }
+                                }
+                            }
                             NestedMeta::Meta(meta) => {
                                 ctx.error_spanned_by(meta, "unexpected meta");
                             }
@@ -97,12 +192,123 @@ impl Container {
             }
         }
 
+        if let (Some(attr), None) = (attrs.iter().find(|a| a.path == ASYNC_XML), &tag_name) {
+            if !alias.is_empty() {
+                ctx.error_spanned_by(attr, "alias requires a tag name given by rename");
+            }
+        }
+        if let (Some(finish), Some(_)) = (&finish, &from) {
+            ctx.error_spanned_by(finish, "finish can't be combined with from");
+        }
+        if let (true, Some(tag_attribute)) = (untagged, &tag_attribute) {
+            ctx.error_spanned_by(
+                tag_attribute,
+                "tag_attribute can't be combined with untagged",
+            );
+        }
+        if let Some(text) = &text {
+            if untagged || tag_attribute.is_some() || xsi_type.is_some() {
+                ctx.error_spanned_by(
+                    text,
+                    "text can't be combined with untagged, tag_attribute or xsi_type",
+                );
+            }
+        }
+
         Self {
             tag_name,
+            alias,
             from: from.unwrap_or(From::Default),
             allow_unknown_children,
             allow_unknown_attributes,
             allow_unknown_text,
+            finish,
+            tag_attribute,
+            case_insensitive,
+            untagged,
+            text: text.is_some(),
+            xsi_type: xsi_type.is_some(),
+            bound,
+            rename_all,
+            rename_all_attributes,
+            ordered,
+        }
+    }
+}
+
+impl Container {
+    /// all element names accepted for the container, starting with its tag name
+    pub fn tag_names(&self) -> Vec<String> {
+        self.tag_name.iter().chain(&self.alias).cloned().collect()
+    }
+}
+
+/// enum variant attributes
+pub struct Variant {
+    pub rename: Option<String>,
+    pub alias: Vec<String>,
+    pub other: bool,
+    pub case_insensitive: bool,
+}
+
+impl Variant {
+    pub fn from_attrs(ctx: &Ctx, attrs: &Vec<Attribute>) -> Self {
+        let mut rename = None;
+        let mut alias = Vec::new();
+        let mut other = false;
+        let mut case_insensitive = false;
+
+        for attr in attrs {
+            if attr.path != ASYNC_XML {
+                continue;
+            }
+            match attr.parse_meta() {
+                Ok(Meta::List(meta)) => {
+                    for nested in meta.nested {
+                        match nested {
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == RENAME => {
+                                let str = get_lit_str(ctx, &m.lit);
+                                if let Ok(str) = str {
+                                    if rename.replace(str.value()).is_some() {
+                                        ctx.error_spanned_by(m, "rename already specified");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == ALIAS => {
+                                let str = get_lit_str(ctx, &m.lit);
+                                if let Ok(str) = str {
+                                    alias.push(str.value());
+                                }
+                            }
+                            NestedMeta::Meta(Meta::Path(m)) if m == OTHER => {
+                                other = true;
+                            }
+                            NestedMeta::Meta(Meta::Path(m)) if m == CASE_INSENSITIVE => {
+                                case_insensitive = true;
+                            }
+                            NestedMeta::Meta(meta) => {
+                                ctx.error_spanned_by(meta, "unexpected meta");
+                            }
+                            NestedMeta::Lit(lit) => {
+                                ctx.error_spanned_by(lit, "unexpected literal");
+                            }
+                        }
+                    }
+                }
+                Ok(other) => {
+                    ctx.error_spanned_by(other, "expected #[from_xml(...)]");
+                }
+                Err(e) => {
+                    ctx.syn_error(e);
+                }
+            }
+        }
+
+        Self {
+            rename,
+            alias,
+            other,
+            case_insensitive,
         }
     }
 }
@@ -111,7 +317,23 @@ pub struct Field {
     pub source: FieldSource,
     pub default: Default,
     pub rename: Option<String>,
+    pub alias: Vec<String>,
     pub from: From,
+    /// function without arguments creating the seed for each child, it has no access to the parent's state
+    pub seed: Option<syn::ExprPath>,
+    pub deserialize_with: Option<syn::ExprPath>,
+    pub collection: bool,
+    pub key: Option<MapKey>,
+    pub deny_duplicate_keys: bool,
+    pub wrapped: Option<String>,
+    pub min_occurs: Option<usize>,
+    pub max_occurs: Option<usize>,
+}
+
+/// where the key of a map field entry is read from
+pub enum MapKey {
+    Attribute(String),
+    Tag,
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -119,10 +341,13 @@ pub enum FieldSource {
     Attribute,
     Child,
     Value,
+    Tag,
     Flatten,
     Remains,
+    OtherAttributes,
 }
 
+#[allow(clippy::enum_variant_names)]
 pub enum Default {
     None,
     Default,
@@ -143,7 +368,16 @@ impl Field {
         let mut source = None;
         let mut default = None;
         let mut rename = None;
+        let mut alias = Vec::new();
         let mut from = None;
+        let mut seed = None;
+        let mut deserialize_with = None;
+        let mut collection = false;
+        let mut key = None;
+        let mut deny_duplicate_keys = false;
+        let mut wrapped = None;
+        let mut min_occurs = None;
+        let mut max_occurs = None;
 
         for attr in attrs {
             if attr.path != ASYNC_XML {
@@ -168,6 +402,51 @@ impl Field {
                                     ctx.error_spanned_by(m, "source already specified");
                                 }
                             }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == CHILD => {
+                                if source.replace(FieldSource::Child).is_some() {
+                                    ctx.error_spanned_by(&m, "source already specified");
+                                }
+                                let str = get_lit_str(ctx, &m.lit);
+                                if let Ok(str) = str {
+                                    if rename.replace(str.value()).is_some() {
+                                        ctx.error_spanned_by(m, "rename already specified");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == CHILD_ATTRIBUTE => {
+                                if source.replace(FieldSource::Child).is_some() {
+                                    ctx.error_spanned_by(&m, "source already specified");
+                                }
+                                let str = get_lit_str(ctx, &m.lit);
+                                if let Ok(str) = str {
+                                    // `child@attribute` is shorthand for the nested path `child/@attribute`
+                                    let path = match str.value().rsplit_once('@') {
+                                        Some((child, attribute)) => {
+                                            format!("{}/@{}", child, attribute)
+                                        }
+                                        None => {
+                                            ctx.error_spanned_by(
+                                                &m,
+                                                "child_attribute must be of the form \"child@attribute\"",
+                                            );
+                                            continue;
+                                        }
+                                    };
+                                    if rename.replace(path).is_some() {
+                                        ctx.error_spanned_by(m, "rename already specified");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::Path(m)) if m == TAG => {
+                                if source.replace(FieldSource::Tag).is_some() {
+                                    ctx.error_spanned_by(m, "source already specified");
+                                }
+                            }
+                            NestedMeta::Meta(Meta::Path(m)) if m == OTHER_ATTRIBUTES => {
+                                if source.replace(FieldSource::OtherAttributes).is_some() {
+                                    ctx.error_spanned_by(m, "source already specified");
+                                }
+                            }
                             NestedMeta::Meta(Meta::Path(m)) if m == FLATTEN => {
                                 if source.replace(FieldSource::Flatten).is_some() {
                                     ctx.error_spanned_by(m, "source already specified");
@@ -178,6 +457,56 @@ impl Field {
                                     ctx.error_spanned_by(m, "source already specified");
                                 }
                             }
+                            NestedMeta::Meta(Meta::Path(m)) if m == COLLECTION => {
+                                collection = true;
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == KEY => {
+                                let str = get_lit_str(ctx, &m.lit);
+                                if let Ok(str) = str {
+                                    let value = str.value();
+                                    let parsed = if value == "tag" {
+                                        MapKey::Tag
+                                    } else if let Some(name) = value.strip_prefix('@') {
+                                        MapKey::Attribute(name.into())
+                                    } else {
+                                        ctx.error_spanned_by(
+                                            &m,
+                                            "key must be either \"tag\" or \"@attribute\"",
+                                        );
+                                        continue;
+                                    };
+                                    if key.replace(parsed).is_some() {
+                                        ctx.error_spanned_by(m, "key already specified");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == WRAPPED => {
+                                let str = get_lit_str(ctx, &m.lit);
+                                if let Ok(str) = str {
+                                    if wrapped.replace(str.value()).is_some() {
+                                        ctx.error_spanned_by(m, "wrapped already specified");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == MIN_OCCURS => {
+                                let int = get_lit_usize(ctx, &m.lit);
+                                if let Ok(int) = int {
+                                    if min_occurs.replace(int).is_some() {
+                                        ctx.error_spanned_by(m, "min_occurs already specified");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == MAX_OCCURS => {
+                                let int = get_lit_usize(ctx, &m.lit);
+                                if let Ok(int) = int {
+                                    if max_occurs.replace(int).is_some() {
+                                        ctx.error_spanned_by(m, "max_occurs already specified");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::Path(m)) if m == DENY_DUPLICATE_KEYS => {
+                                deny_duplicate_keys = true;
+                            }
                             NestedMeta::Meta(Meta::Path(m)) if m == DEFAULT => {
                                 if default.replace(Default::Default).is_some() {
                                     ctx.error_spanned_by(m, "default already specified");
@@ -199,6 +528,12 @@ impl Field {
                                     }
                                 }
                             }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == ALIAS => {
+                                let str = get_lit_str(ctx, &m.lit);
+                                if let Ok(str) = str {
+                                    alias.push(str.value());
+                                }
+                            }
                             NestedMeta::Meta(Meta::Path(m)) if m == FROM_STR => {
                                 if from.replace(From::FromStr).is_some() {
                                     ctx.error_spanned_by(m, "from already specified");
@@ -220,6 +555,39 @@ impl Field {
                                     }
                                 }
                             }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == SEED => {
+                                let path = get_lit_str_as_expr_path(ctx, &m.lit);
+                                if let Ok(path) = path {
+                                    if seed.replace(path).is_some() {
+                                        ctx.error_spanned_by(m, "seed already specified");
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == DESERIALIZE_WITH => {
+                                let path = get_lit_str_as_expr_path(ctx, &m.lit);
+                                if let Ok(path) = path {
+                                    if deserialize_with.replace(path).is_some() {
+                                        ctx.error_spanned_by(
+                                            m,
+                                            "deserialize_with already specified",
+                                        );
+                                    }
+                                }
+                            }
+                            NestedMeta::Meta(Meta::NameValue(m)) if m.path == WITH => {
+                                let path = get_lit_str_as_expr_path(ctx, &m.lit);
+                                if let Ok(mut path) = path {
+                                    path.path.segments.push(
+                                        syn::Ident::new("deserialize", Span::call_site()).into(),
+                                    );
+                                    if deserialize_with.replace(path).is_some() {
+                                        ctx.error_spanned_by(
+                                            m,
+                                            "deserialize_with already specified",
+                                        );
+                                    }
+                                }
+                            }
                             NestedMeta::Meta(meta) => {
                                 ctx.error_spanned_by(meta, "unexpected meta");
                             }
@@ -242,7 +610,16 @@ impl Field {
             source: source.unwrap_or(FieldSource::Value),
             default: default.unwrap_or(Default::None),
             rename,
+            alias,
             from: from.unwrap_or(From::Default),
+            seed,
+            deserialize_with,
+            collection,
+            key,
+            deny_duplicate_keys,
+            wrapped,
+            min_occurs,
+            max_occurs,
         }
     }
 }
@@ -256,6 +633,15 @@ fn get_lit_str<'a>(ctx: &Ctx, lit: &'a syn::Lit) -> Result<&'a syn::LitStr, ()>
     }
 }
 
+fn get_lit_usize(ctx: &Ctx, lit: &syn::Lit) -> Result<usize, ()> {
+    if let syn::Lit::Int(lit_int) = lit {
+        lit_int.base10_parse().map_err(|e| ctx.syn_error(e))
+    } else {
+        ctx.error_spanned_by(lit, "expected integer literal");
+        Err(())
+    }
+}
+
 fn get_lit_str_as_expr_path(ctx: &Ctx, lit: &syn::Lit) -> Result<syn::ExprPath, ()> {
     let str = get_lit_str(ctx, lit)?;
     parse_lit_str(str).map_err(|_| {
@@ -270,6 +656,30 @@ fn get_lit_str_as_type(ctx: &Ctx, lit: &syn::Lit) -> Result<syn::Type, ()> {
     })
 }
 
+fn get_lit_str_as_rename_rule(ctx: &Ctx, lit: &syn::Lit) -> Result<RenameRule, ()> {
+    let str = get_lit_str(ctx, lit)?;
+    str.value().parse().map_err(|e: String| {
+        ctx.error_spanned_by(lit, e);
+    })
+}
+
+fn get_lit_str_as_where_predicates(
+    ctx: &Ctx,
+    lit: &syn::Lit,
+) -> Result<Vec<syn::WherePredicate>, ()> {
+    let str = get_lit_str(ctx, lit)?;
+    let tokens = spanned_tokens(str).map_err(|_| {
+        ctx.error_spanned_by(lit, "failed to parse where predicates");
+    })?;
+    let parser =
+        syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated;
+    syn::parse::Parser::parse2(parser, tokens)
+        .map(|predicates| predicates.into_iter().collect())
+        .map_err(|_| {
+            ctx.error_spanned_by(lit, "failed to parse where predicates");
+        })
+}
+
 fn spanned_tokens(s: &syn::LitStr) -> syn::parse::Result<TokenStream> {
     let stream = syn::parse_str(&s.value())?;
     Ok(respan(stream, s.span()))
@@ -0,0 +1,33 @@
+use syn::{parse_quote, GenericParam, Generics, WherePredicate};
+
+/// Extend the generics of the deserialized type with the reader type parameter `B`
+///
+/// `bound` replaces the inferred bounds, which require every type parameter to implement `FromXml<B>` if `infer`
+/// is set.
+pub fn with_reader(generics: &Generics, bound: Option<&[WherePredicate]>, infer: bool) -> Generics {
+    let mut generics = generics.clone();
+    let predicates = match bound {
+        Some(bound) => bound.to_vec(),
+        None if infer => generics
+            .type_params()
+            .map(|param| {
+                let ident = &param.ident;
+                parse_quote!(#ident: ::async_xml::reader::FromXml<B>)
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    generics.make_where_clause().predicates.extend(predicates);
+
+    // lifetimes have to come before type parameters
+    let lifetimes = generics
+        .params
+        .iter()
+        .take_while(|param| matches!(param, GenericParam::Lifetime(_)))
+        .count();
+    generics.params.insert(
+        lifetimes,
+        parse_quote!(B: ::tokio::io::AsyncBufRead + Unpin),
+    );
+    generics
+}
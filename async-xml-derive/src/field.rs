@@ -1,6 +1,6 @@
 use crate::attr::{self, Field, FieldSource};
 use crate::ctx::Ctx;
-use crate::path::{get_generic_arg, get_type_path_type, TypePathType};
+use crate::path::{generic_arg, get_generic_arg, get_type_path_type, is_wrapper, TypePathType};
 use crate::xml_struct::StructType;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, TokenStreamExt};
@@ -23,39 +23,152 @@ pub struct FieldData<'a> {
     pub field_type: FieldType,
     /// the kind of type this field is
     pub type_type: TypePathType,
+    /// pointer type wrapping each deserialized value, like `Box<T>`
+    pub wrapper: Option<Type>,
     /// name of the corresponding field in the generated visitor struct
     pub visitor_field_name: Ident,
     /// type of the corresponding field in the generated visitor struct
     pub visitor_field_type: Type,
     /// xml element name to deserialize into this field
     pub tag_name: Lit,
+    /// attributes of the child element deserialized into other fields of the parent
+    pub child_attributes: Vec<String>,
+    /// position of this field in the expected order of child elements, if the order is enforced
+    pub order: Option<usize>,
+    /// position of this field among the positional child elements of a tuple struct
+    pub position: Option<usize>,
 }
 
 impl<'a> FieldData<'a> {
-    pub fn from_field(ctx: &Ctx, field: &'a syn::Field, index: usize) -> Result<Self, ()> {
-        let attrs = Field::from_attrs(ctx, &field.attrs);
-        let inner_de_type = match &attrs.from {
-            attr::From::Default => &field.ty,
-            attr::From::From(t) => t,
-            attr::From::FromStr => unimplemented!(),
-            attr::From::TryFrom(_t) => unimplemented!(),
+    pub fn from_field(
+        ctx: &Ctx,
+        container: &attr::Container,
+        field: &'a syn::Field,
+        index: usize,
+    ) -> Result<Self, ()> {
+        let mut attrs = Field::from_attrs(ctx, &field.attrs);
+        let paths = attrs.rename.iter().chain(&attrs.alias);
+        if paths.clone().any(|p| p.contains('/')) {
+            if attrs.source != FieldSource::Child {
+                ctx.error_spanned_by(field, "nested paths are only valid for child elements");
+                return Err(());
+            }
+            if !paths.clone().all(|p| is_valid_path(p)) {
+                ctx.error_spanned_by(field, "invalid nested path");
+                return Err(());
+            }
+            // paths like `a/b/@c` select an attribute of a nested element
+            let attribute_paths = paths.filter(|p| p.contains("/@")).count();
+            if attribute_paths > 0 {
+                if attribute_paths != 1 + attrs.alias.len() {
+                    ctx.error_spanned_by(
+                        field,
+                        "nested paths must either all select attributes or child elements",
+                    );
+                    return Err(());
+                }
+                attrs.source = FieldSource::Attribute;
+            }
+        }
+        // try_from converts every element of options and collections on its own
+        let try_from_item = match &attrs.from {
+            attr::From::TryFrom(t) => Some(match get_type_path_type(t) {
+                TypePathType::Option | TypePathType::Vec => get_generic_arg(t),
+                _ => t.clone(),
+            }),
+            _ => None,
+        };
+        let inner_de_type = match (&attrs.from, &try_from_item) {
+            (attr::From::Default | attr::From::FromStr, _) => field.ty.clone(),
+            (attr::From::TryFrom(_), Some(item))
+                if get_type_path_type(&field.ty) == TypePathType::Option =>
+            {
+                syn::parse2(quote! { Option<#item> }).unwrap()
+            }
+            (attr::From::TryFrom(_), Some(_))
+                if attrs.collection || get_type_path_type(&field.ty) == TypePathType::Vec =>
+            {
+                field.ty.clone()
+            }
+            (attr::From::From(t) | attr::From::TryFrom(t), _) => t.clone(),
+        };
+        let inner_de_type = &inner_de_type;
+        let type_type = if attrs.key.is_some() {
+            TypePathType::Map
+        } else if get_type_path_type(inner_de_type) == TypePathType::Option
+            && (attrs.collection
+                || matches!(
+                    generic_arg(inner_de_type).as_ref().map(get_type_path_type),
+                    Some(TypePathType::Vec)
+                ))
+        {
+            TypePathType::OptionalVec
+        } else if attrs.collection {
+            TypePathType::Vec
+        } else {
+            get_type_path_type(inner_de_type)
+        };
+        // the collection type of an optional collection
+        let collection_type = match type_type {
+            TypePathType::Vec => Some(inner_de_type.clone()),
+            TypePathType::OptionalVec => generic_arg(inner_de_type),
+            _ => None,
         };
-        let type_type = get_type_path_type(inner_de_type);
         let type_type = match (attrs.source, type_type) {
             (FieldSource::Flatten, TypePathType::Option) => TypePathType::OptionalNode,
             (FieldSource::Flatten, TypePathType::Any) => TypePathType::XmlNode,
             (FieldSource::Child, TypePathType::XmlNode) => TypePathType::Any,
             _ => type_type,
         };
-        let deserialization_type = match type_type {
-            TypePathType::Any | TypePathType::Option | TypePathType::XmlNode => {
+        // values behind a pointer are deserialized as the pointee and wrapped afterwards
+        let wrapper = match type_type {
+            _ if attrs.deserialize_with.is_some()
+                || attrs.seed.is_some()
+                || attrs.wrapped.is_some()
+                || try_from_item.is_some() =>
+            {
+                None
+            }
+            TypePathType::Any => Some(inner_de_type.clone()),
+            TypePathType::Option => generic_arg(inner_de_type),
+            TypePathType::Vec | TypePathType::OptionalVec => {
+                collection_type.as_ref().and_then(generic_arg)
+            }
+            TypePathType::Map | TypePathType::XmlNode | TypePathType::OptionalNode => None,
+        }
+        .filter(is_wrapper);
+        let deserialization_type = match (type_type, &wrapper) {
+            (TypePathType::Option, Some(wrapper)) => syn::parse2(
+                quote! { Option<<#wrapper as ::async_xml::collection::XmlWrapper>::Inner> },
+            )
+            .unwrap(),
+            (TypePathType::Any | TypePathType::Vec | TypePathType::OptionalVec, Some(wrapper)) => {
+                syn::parse2(quote! { <#wrapper as ::async_xml::collection::XmlWrapper>::Inner })
+                    .unwrap()
+            }
+            (TypePathType::Any | TypePathType::Option | TypePathType::XmlNode, _) => {
                 inner_de_type.clone()
             }
-            TypePathType::Vec | TypePathType::OptionalNode => get_generic_arg(inner_de_type),
+            (TypePathType::Vec, None) if try_from_item.is_some() => try_from_item.clone().unwrap(),
+            (TypePathType::Vec | TypePathType::OptionalVec, None) => {
+                let collection_type = collection_type.as_ref().unwrap();
+                syn::parse2(
+                    quote! { <#collection_type as ::async_xml::collection::XmlCollection>::Item },
+                )
+                .unwrap()
+            }
+            (TypePathType::Map, _) => {
+                syn::parse2(quote! { <#inner_de_type as ::async_xml::collection::XmlMap>::Value })
+                    .unwrap()
+            }
+            (TypePathType::OptionalNode, _) => get_generic_arg(inner_de_type),
         };
         let visitor_field_type = match type_type {
             TypePathType::Any => syn::parse2(quote! { Option<#inner_de_type> }).unwrap(),
-            TypePathType::Vec | TypePathType::Option => inner_de_type.to_owned(),
+            TypePathType::Vec
+            | TypePathType::OptionalVec
+            | TypePathType::Map
+            | TypePathType::Option => inner_de_type.to_owned(),
             TypePathType::XmlNode | TypePathType::OptionalNode => syn::parse2(
                 quote! { <#deserialization_type as ::async_xml::reader::FromXml<B>>::Visitor },
             )
@@ -64,7 +177,11 @@ impl<'a> FieldData<'a> {
 
         match (type_type, attrs.source) {
             // allow child elements to be read into a vec
-            (TypePathType::Vec, FieldSource::Child) => {}
+            (TypePathType::Vec | TypePathType::OptionalVec, FieldSource::Child) => {}
+            // allow child elements to be read into a map
+            (TypePathType::Map, FieldSource::Child) => {}
+            // allow unknown attributes to be collected into a vec or map
+            (TypePathType::Vec | TypePathType::Map, FieldSource::OtherAttributes) => {}
             // allow xmlnode remains and flatten
             (TypePathType::XmlNode, FieldSource::Remains) => {}
             (TypePathType::XmlNode, FieldSource::Flatten) => {}
@@ -81,6 +198,145 @@ impl<'a> FieldData<'a> {
             }
         }
 
+        if try_from_item.is_some()
+            && !matches!(
+                type_type,
+                TypePathType::Any | TypePathType::Option | TypePathType::Vec
+            )
+        {
+            ctx.error_spanned_by(
+                field,
+                "try_from is only valid for single values, options and collections",
+            );
+            return Err(());
+        }
+
+        if type_type == TypePathType::Map && attrs.source != FieldSource::OtherAttributes {
+            if attrs.key.is_none() {
+                ctx.error_spanned_by(field, "map fields require a key");
+                return Err(());
+            }
+            if attrs.collection {
+                ctx.error_spanned_by(field, "key can't be combined with collection");
+                return Err(());
+            }
+            if matches!(attrs.key, Some(attr::MapKey::Tag))
+                && (attrs.rename.is_some() || !attrs.alias.is_empty())
+            {
+                ctx.error_spanned_by(
+                    field,
+                    "key = \"tag\" collects all unknown children and can't be given a child name",
+                );
+                return Err(());
+            }
+            if attrs.deserialize_with.is_some()
+                || attrs.seed.is_some()
+                || matches!(attrs.from, attr::From::FromStr)
+            {
+                ctx.error_spanned_by(
+                    field,
+                    "map fields can't be combined with deserialize_with, seed or from_str",
+                );
+                return Err(());
+            }
+        } else if attrs.deny_duplicate_keys {
+            ctx.error_spanned_by(field, "deny_duplicate_keys is only valid for map fields");
+            return Err(());
+        }
+
+        if attrs.min_occurs.is_some() || attrs.max_occurs.is_some() {
+            if !matches!(
+                type_type,
+                TypePathType::Vec | TypePathType::OptionalVec | TypePathType::Map
+            ) || attrs.source != FieldSource::Child
+            {
+                ctx.error_spanned_by(
+                    field,
+                    "min_occurs and max_occurs are only valid for collections of child elements",
+                );
+                return Err(());
+            }
+            if let (Some(min), Some(max)) = (attrs.min_occurs, attrs.max_occurs) {
+                if min > max {
+                    ctx.error_spanned_by(field, "min_occurs must not be greater than max_occurs");
+                    return Err(());
+                }
+            }
+        }
+
+        if attrs.wrapped.is_some() {
+            if !matches!(type_type, TypePathType::Vec | TypePathType::OptionalVec)
+                || attrs.source != FieldSource::Child
+            {
+                ctx.error_spanned_by(
+                    field,
+                    "wrapped is only valid for collections of child elements",
+                );
+                return Err(());
+            }
+            if attrs.deserialize_with.is_some()
+                || attrs.seed.is_some()
+                || matches!(attrs.from, attr::From::FromStr | attr::From::TryFrom(_))
+            {
+                ctx.error_spanned_by(
+                    field,
+                    "wrapped can't be combined with deserialize_with, seed, from_str or try_from",
+                );
+                return Err(());
+            }
+        }
+
+        if !attrs.alias.is_empty()
+            && !matches!(attrs.source, FieldSource::Attribute | FieldSource::Child)
+        {
+            ctx.error_spanned_by(
+                field,
+                "alias is only valid for attributes and child elements",
+            );
+            return Err(());
+        }
+
+        if attrs.deserialize_with.is_some()
+            && matches!(
+                attrs.source,
+                FieldSource::Flatten | FieldSource::Remains | FieldSource::OtherAttributes
+            )
+        {
+            ctx.error_spanned_by(
+                field,
+                "deserialize_with is only valid for attributes, values, tags and child elements",
+            );
+            return Err(());
+        }
+        if let attr::From::FromStr = attrs.from {
+            if matches!(
+                attrs.source,
+                FieldSource::Flatten | FieldSource::Remains | FieldSource::OtherAttributes
+            ) {
+                ctx.error_spanned_by(
+                    field,
+                    "from_str is only valid for attributes, values, tags and child elements",
+                );
+                return Err(());
+            }
+            if attrs.deserialize_with.is_some() || attrs.seed.is_some() {
+                ctx.error_spanned_by(
+                    field,
+                    "from_str can't be combined with deserialize_with or seed",
+                );
+                return Err(());
+            }
+        }
+        if attrs.deserialize_with.is_some() && attrs.seed.is_some() {
+            ctx.error_spanned_by(field, "deserialize_with can't be combined with seed");
+            return Err(());
+        }
+
+        if attrs.seed.is_some() && attrs.source != FieldSource::Child {
+            ctx.error_spanned_by(field, "seed is only valid for child elements");
+            return Err(());
+        }
+
         let (visitor_field_name, field_type) = if let Some(ident) = field.ident.as_ref() {
             (ident.to_owned(), FieldType::Named)
         } else {
@@ -89,8 +345,17 @@ impl<'a> FieldData<'a> {
                 FieldType::Unnamed,
             )
         };
+        let rename_rule = match attrs.source {
+            FieldSource::Attribute => container.rename_all_attributes.or(container.rename_all),
+            _ => container.rename_all,
+        };
         let tag_name = if let Some(rename) = &attrs.rename {
             syn::LitStr::new(rename, Span::call_site())
+        } else if let (Some(rule), FieldType::Named) = (rename_rule, field_type) {
+            syn::LitStr::new(
+                &rule.apply_to_field(&visitor_field_name.to_string()),
+                Span::call_site(),
+            )
         } else {
             syn::LitStr::new(&visitor_field_name.to_string(), Span::call_site())
         };
@@ -102,12 +367,49 @@ impl<'a> FieldData<'a> {
             deserialization_type,
             field_type,
             type_type,
+            wrapper,
             visitor_field_name,
             visitor_field_type,
             tag_name,
+            child_attributes: Vec::new(),
+            order: None,
+            position: None,
         })
     }
 
+    /// whether this field consumes child elements by position when part of a tuple struct
+    pub fn is_positional(&self) -> bool {
+        self.attrs.source == FieldSource::Child
+            && self.field_type == FieldType::Unnamed
+            && self.attrs.rename.is_none()
+            && self.attrs.alias.is_empty()
+    }
+
+    /// the tag name and all aliases of this field
+    pub fn tag_names(&self) -> Vec<String> {
+        let tag = match &self.tag_name {
+            Lit::Str(tag) => tag.value(),
+            _ => unreachable!("tag names are always strings"),
+        };
+        std::iter::once(tag)
+            .chain(self.attrs.alias.iter().cloned())
+            .collect()
+    }
+
+    /// intermediate elements the nested paths of this field descend through, e.g. `a` and `a/b` for `a/b/c`
+    pub fn path_prefixes(&self) -> Vec<String> {
+        let mut prefixes = Vec::new();
+        for path in &self.tag_names() {
+            let mut end = 0;
+            while let Some(i) = path[end..].find('/') {
+                end += i;
+                prefixes.push(path[..end].to_string());
+                end += 1;
+            }
+        }
+        prefixes
+    }
+
     pub fn visitor_struct_field_def(&self) -> TokenStream {
         let name = &self.visitor_field_name;
         let ty = &self.visitor_field_type;
@@ -117,13 +419,13 @@ impl<'a> FieldData<'a> {
     pub fn visitor_struct_default_field(&self) -> TokenStream {
         let name = &self.visitor_field_name;
         match self.type_type {
-            TypePathType::Vec => {
-                quote! { #name: Vec::new(), }
-            }
-            TypePathType::Any | TypePathType::Option => {
+            TypePathType::Any | TypePathType::Option | TypePathType::OptionalVec => {
                 quote! { #name: None, }
             }
-            TypePathType::XmlNode | TypePathType::OptionalNode => {
+            TypePathType::Vec
+            | TypePathType::Map
+            | TypePathType::XmlNode
+            | TypePathType::OptionalNode => {
                 quote! { #name: Default::default(), }
             }
         }
@@ -139,44 +441,90 @@ impl<'a> FieldData<'a> {
         visit_tag: &mut TokenStream,
     ) {
         let tag = &self.tag_name;
+        let aliases = &self.attrs.alias;
+        let pattern = quote! { #tag #(| #aliases)* };
         let ident = &self.visitor_field_name;
-        let ty = &self.deserialization_type;
         let field_ty = &self.visitor_field_type;
+        let order = self.order.map(|order| {
+            quote! {
+                if self.__order > #order {
+                    return Err(::async_xml::Error::UnexpectedOrder(name.into()));
+                }
+                self.__order = #order;
+            }
+        });
+        // positional fields consume the next child element regardless of its name, and report that name in errors
+        let (pattern, order, tag) = match self.position {
+            Some(position) => (
+                quote! { _ if self.__position == #position },
+                Some(quote! {
+                    #order
+                    self.__position += 1;
+                }),
+                quote! { name },
+            ),
+            None => (pattern, order, quote! { #tag }),
+        };
         match self.attrs.source {
             FieldSource::Attribute => {
-                let build_val = quote! {
-                    let mut visitor = <#ty as ::async_xml::reader::FromXml<B>>::Visitor::default();
-                    <<#ty as ::async_xml::reader::FromXml<B>>::Visitor as ::async_xml::reader::Visitor<B>>::visit_text(&mut visitor, value)?;
-                    let val = <<#ty as ::async_xml::reader::FromXml<B>>::Visitor as ::async_xml::reader::Visitor<B>>::build(visitor)?;
-                };
+                let build_val = self.build_from_str(quote!(value));
                 match self.type_type {
                     TypePathType::Any => {
                         visit_attr.append_all(quote! {
-                           #tag => {
+                           #pattern => {
                                 #build_val
-                                self.#ident.replace(val);
+                                if self.#ident.replace(val).is_some() {
+                                    return Err(::async_xml::Error::DoubleAttribute(name.into()));
+                                }
                             }
                         });
                     }
                     TypePathType::Option => {
                         visit_attr.append_all(quote! {
-                           #tag => {
+                           #pattern => {
+                                if self.#ident.is_some() {
+                                    return Err(::async_xml::Error::DoubleAttribute(name.into()));
+                                }
                                 #build_val
                                 self.#ident = val;
                             }
                         });
                     }
-                    TypePathType::Vec | TypePathType::XmlNode | TypePathType::OptionalNode => {
-                        unreachable!("vec and xmlnode aren't valid for attribute")
+                    TypePathType::Vec
+                    | TypePathType::OptionalVec
+                    | TypePathType::Map
+                    | TypePathType::XmlNode
+                    | TypePathType::OptionalNode => {
+                        unreachable!("vec, map and xmlnode aren't valid for attribute")
+                    }
+                }
+            }
+            FieldSource::Tag => {
+                let build_val = self.build_from_str(quote!(name));
+                match self.type_type {
+                    TypePathType::Any => {
+                        visit_tag.append_all(quote! {
+                            #build_val
+                            self.#ident.replace(val);
+                        });
+                    }
+                    TypePathType::Option => {
+                        visit_tag.append_all(quote! {
+                            #build_val
+                            self.#ident = val;
+                        });
+                    }
+                    TypePathType::Vec
+                    | TypePathType::OptionalVec
+                    | TypePathType::Map
+                    | TypePathType::XmlNode
+                    | TypePathType::OptionalNode => {
+                        unreachable!("vec, map and xmlnode aren't valid for tag")
                     }
                 }
             }
             FieldSource::Value => {
-                let build_val = quote! {
-                    let mut visitor = <#ty as ::async_xml::reader::FromXml<B>>::Visitor::default();
-                    <<#ty as ::async_xml::reader::FromXml<B>>::Visitor as ::async_xml::reader::Visitor<B>>::visit_text(&mut visitor, text)?;
-                    let val = <<#ty as ::async_xml::reader::FromXml<B>>::Visitor as ::async_xml::reader::Visitor<B>>::build(visitor)?;
-                };
+                let build_val = self.build_from_str(quote!(text));
                 match self.type_type {
                     TypePathType::Any => {
                         visit_text.append_all(quote! {
@@ -197,11 +545,40 @@ impl<'a> FieldData<'a> {
                             self.#ident = val;
                         });
                     }
-                    TypePathType::Vec | TypePathType::XmlNode | TypePathType::OptionalNode => {
-                        unreachable!("vec and xmlnode aren't valid for value")
+                    TypePathType::Vec
+                    | TypePathType::OptionalVec
+                    | TypePathType::Map
+                    | TypePathType::XmlNode
+                    | TypePathType::OptionalNode => {
+                        unreachable!("vec, map and xmlnode aren't valid for value")
                     }
                 }
             }
+            FieldSource::OtherAttributes => {
+                let insert = match self.type_type {
+                    TypePathType::Map => quote! {
+                        ::async_xml::collection::XmlMap::insert_entry(&mut self.#ident, name.into(), value.into());
+                    },
+                    TypePathType::Vec => quote! {
+                        ::async_xml::collection::XmlCollection::push_item(
+                            &mut self.#ident,
+                            ::async_xml::util::XmlAttribute {
+                                name: name.into(),
+                                value: value.into(),
+                            },
+                        );
+                    },
+                    _ => unreachable!("other attributes are always collected into a vec or map"),
+                };
+                // namespace declarations and schema instance attributes aren't collected, just like they're accepted
+                // without other_attributes
+                visit_attr_any.append_all(quote! {
+                    if !::async_xml::util::is_reserved_attribute(name) {
+                        #insert
+                    }
+                    return Ok(());
+                });
+            }
             FieldSource::Remains | FieldSource::Flatten => {
                 visit_attr_any.append_all(quote! {
                     // ignore unexpected attribute errors here to maybe pass on to remains/other flattens
@@ -229,53 +606,267 @@ impl<'a> FieldData<'a> {
                 });
             }
             FieldSource::Child => match self.type_type {
-                TypePathType::Vec => {
+                TypePathType::Vec | TypePathType::OptionalVec if self.attrs.wrapped.is_some() => {
+                    let item = self.attrs.wrapped.as_ref().unwrap();
+                    let (take, store) = if self.type_type == TypePathType::OptionalVec {
+                        (
+                            quote! { self.#ident.take().unwrap_or_default() },
+                            quote! { Some(collection) },
+                        )
+                    } else {
+                        (
+                            quote! { ::core::mem::take(&mut self.#ident) },
+                            quote! { collection },
+                        )
+                    };
                     visit_child.append_all(quote! {
-                        #tag => {
-                            self.#ident.push(reader.deserialize::<#ty>().await.map_err(|e| {
+                        #pattern => {
+                            #order
+                            let visitor = ::async_xml::collection::WrappedVisitor::new(#take, #item);
+                            let collection = reader.deserialize_with_visitor(visitor).await.map_err(|e| {
                                 ::async_xml::Error::InnerDeserialiaztionError(#tag.into(), Box::new(e))
-                            })?);
+                            })?;
+                            self.#ident = #store;
+                        }
+                    });
+                }
+                TypePathType::Vec | TypePathType::OptionalVec => {
+                    let collection = if self.type_type == TypePathType::OptionalVec {
+                        quote! { self.#ident.get_or_insert_with(Default::default) }
+                    } else {
+                        quote! { &mut self.#ident }
+                    };
+                    let deserialize = self.deserialize_child();
+                    let val = self.wrap(quote! {
+                        #deserialize.await.map_err(|e| {
+                            ::async_xml::Error::InnerDeserialiaztionError(#tag.into(), Box::new(e))
+                        })?
+                    });
+                    let convert = if let attr::From::TryFrom(_) = self.attrs.from {
+                        let convert = self.try_convert(quote!(val), Some(&tag));
+                        quote! { let val = #convert?; }
+                    } else {
+                        quote! {}
+                    };
+                    visit_child.append_all(quote! {
+                        #pattern => {
+                            #order
+                            let val = #val;
+                            #convert
+                            ::async_xml::collection::XmlCollection::push_item(#collection, val);
                         }
                     });
                 }
                 TypePathType::Any => {
+                    let deserialize = self.deserialize_child();
+                    let val = self.wrap(quote! {
+                        #deserialize.await.map_err(|e| {
+                            ::async_xml::Error::InnerDeserialiaztionError(#tag.into(), Box::new(e))
+                        })?
+                    });
                     visit_child.append_all(quote! {
-                        #tag => {
+                        #pattern => {
+                            #order
                             if self.#ident.is_some() {
                                 return Err(::async_xml::Error::DoubleChild(name.into()));
                             }
-                            self.#ident = Some(reader.deserialize::<#ty>().await.map_err(|e| {
-                                ::async_xml::Error::InnerDeserialiaztionError(#tag.into(), Box::new(e))
-                            })?);
+                            self.#ident = Some(#val);
                         }
                     });
                 }
                 TypePathType::Option => {
+                    let deserialize = self.deserialize_child();
+                    let val = self.wrap(quote! {
+                        #deserialize.await.map_err(|e| {
+                            ::async_xml::Error::InnerDeserialiaztionError(#tag.into(), Box::new(e))
+                        })?
+                    });
                     visit_child.append_all(quote! {
-                        #tag => {
+                        #pattern => {
+                            #order
                             if self.#ident.is_some() {
                                 return Err(::async_xml::Error::DoubleChild(name.into()));
                             }
-                            self.#ident = reader.deserialize::<#ty>().await.map_err(|e| {
-                                ::async_xml::Error::InnerDeserialiaztionError(#tag.into(), Box::new(e))
-                            })?;
+                            self.#ident = #val;
                         }
                     });
                 }
+                TypePathType::Map => {
+                    let insert = self.insert_map_entry();
+                    match &self.attrs.key {
+                        Some(attr::MapKey::Tag) => visit_child_any.append_all(quote! {
+                            #order
+                            #insert
+                            return Ok(());
+                        }),
+                        _ => visit_child.append_all(quote! {
+                            #pattern => {
+                                #order
+                                #insert
+                            }
+                        }),
+                    }
+                }
                 TypePathType::XmlNode | TypePathType::OptionalNode => unreachable!(),
             },
         }
     }
 
-    pub fn visitor_build(&self) -> TokenStream {
+    /// statements deserializing the current child element into an entry of this map field
+    fn insert_map_entry(&self) -> TokenStream {
+        let ident = &self.visitor_field_name;
+        let map_ty = &self.visitor_field_type;
+        let value_ty = &self.deserialization_type;
+        let map_key = match &self.attrs.key {
+            Some(attr::MapKey::Attribute(name)) => {
+                quote! { ::async_xml::collection::MapKey::Attribute(#name) }
+            }
+            Some(attr::MapKey::Tag) => quote! { ::async_xml::collection::MapKey::Tag },
+            None => unreachable!("map fields always have a key"),
+        };
+        let insert = quote! {
+            ::async_xml::collection::XmlMap::insert_entry(&mut self.#ident, parsed_key, val)
+        };
+        let insert = if self.attrs.deny_duplicate_keys {
+            quote! {
+                if #insert {
+                    return Err(::async_xml::Error::DuplicateKey(key));
+                }
+            }
+        } else {
+            quote! { #insert; }
+        };
+        quote! {
+            let visitor = ::async_xml::collection::MapEntryVisitor::new(
+                <#value_ty as ::async_xml::reader::FromXml<B>>::Visitor::default(),
+                #map_key,
+            );
+            let (key, val) = reader.deserialize_with_visitor(visitor).await.map_err(|e| {
+                ::async_xml::Error::InnerDeserialiaztionError(name.into(), Box::new(e))
+            })?;
+            let mut visitor = <<#map_ty as ::async_xml::collection::XmlMap>::Key as ::async_xml::reader::FromXml<B>>::Visitor::default();
+            ::async_xml::reader::Visitor::<B>::visit_text(&mut visitor, &key)?;
+            let parsed_key = ::async_xml::reader::Visitor::<B>::build(visitor)?;
+            #insert
+        }
+    }
+
+    /// statements building `val` from the string expression `input`
+    fn build_from_str(&self, input: TokenStream) -> TokenStream {
+        let build = self.build_from_str_inner(input);
+        if self.wrapper.is_some() {
+            let val = self.wrap(quote!(val));
+            quote! {
+                #build
+                let val = #val;
+            }
+        } else {
+            build
+        }
+    }
+
+    /// expression wrapping the deserialized value `val` into this field's pointer type, if any
+    fn wrap(&self, val: TokenStream) -> TokenStream {
+        match &self.wrapper {
+            Some(wrapper) if self.type_type == TypePathType::Option => {
+                quote! { #val.map(<#wrapper as ::async_xml::collection::XmlWrapper>::wrap) }
+            }
+            Some(wrapper) => {
+                quote! { <#wrapper as ::async_xml::collection::XmlWrapper>::wrap(#val) }
+            }
+            None => val,
+        }
+    }
+
+    fn build_from_str_inner(&self, input: TokenStream) -> TokenStream {
+        let ty = &self.deserialization_type;
+        if let Some(deserialize_with) = &self.attrs.deserialize_with {
+            quote! {
+                let val = #deserialize_with(#input)?;
+            }
+        } else if let attr::From::FromStr = self.attrs.from {
+            if self.type_type == TypePathType::Option {
+                let parse = self.parse_str(&get_generic_arg(ty), input);
+                quote! { let val = Some(#parse?); }
+            } else {
+                let parse = self.parse_str(ty, input);
+                quote! { let val = #parse?; }
+            }
+        } else {
+            quote! {
+                let mut visitor = <#ty as ::async_xml::reader::FromXml<B>>::Visitor::default();
+                <<#ty as ::async_xml::reader::FromXml<B>>::Visitor as ::async_xml::reader::Visitor<B>>::visit_text(&mut visitor, #input)?;
+                let val = <<#ty as ::async_xml::reader::FromXml<B>>::Visitor as ::async_xml::reader::Visitor<B>>::build(visitor)?;
+            }
+        }
+    }
+
+    /// expression parsing the string expression `input` into a `Result` of `ty` using [`FromStr`](std::str::FromStr)
+    fn parse_str(&self, ty: &Type, input: TokenStream) -> TokenStream {
+        quote! {
+            <#ty as ::core::str::FromStr>::from_str(#input)
+                .map_err(|e| ::async_xml::Error::Deserialization(e.to_string()))
+        }
+    }
+
+    /// expression deserializing the current child element, not yet awaited
+    fn deserialize_child(&self) -> TokenStream {
+        let ty = &self.deserialization_type;
+        if !self.child_attributes.is_empty() {
+            // pass the attributes selected by other fields back to this visitor
+            let attributes = &self.child_attributes;
+            quote! {
+                {
+                    let visitor = ::async_xml::util::ChildAttributeVisitor::new(
+                        self,
+                        <#ty as ::async_xml::reader::FromXml<B>>::Visitor::default(),
+                        name,
+                        &[#(#attributes),*],
+                    );
+                    reader.deserialize_with_visitor(visitor)
+                }
+            }
+        } else if let Some(seed) = &self.attrs.seed {
+            quote! { reader.deserialize_seed(#seed()) }
+        } else if let Some(deserialize_with) = &self.attrs.deserialize_with {
+            quote! { #deserialize_with(reader) }
+        } else if let attr::From::FromStr = self.attrs.from {
+            // parse the text content of the child element
+            let parse = if self.type_type == TypePathType::Option {
+                let parse = self.parse_str(&get_generic_arg(ty), quote!(&text));
+                quote! { #parse.map(Some) }
+            } else {
+                self.parse_str(ty, quote!(&text))
+            };
+            quote! {
+                async {
+                    let text: String = reader.deserialize().await?;
+                    #parse
+                }
+            }
+        } else {
+            quote! { reader.deserialize::<#ty>() }
+        }
+    }
+
+    /// statements building this field's value, `finish` selects whether flattened visitors are finished asynchronously
+    pub fn visitor_build(&self, finish: bool) -> TokenStream {
+        let name = &self.visitor_field_name;
+        let ty = &self.visitor_field_type;
+        let build_inner = if finish {
+            quote! { <#ty as ::async_xml::reader::Visitor<B>>::finish(self.#name).await }
+        } else {
+            quote! { <#ty as ::async_xml::reader::Visitor<B>>::build(self.#name) }
+        };
         match self.type_type {
-            TypePathType::Vec | TypePathType::Option => TokenStream::new(),
+            TypePathType::Vec | TypePathType::OptionalVec | TypePathType::Map => {
+                self.build_occurs()
+            }
+            TypePathType::Option => TokenStream::new(),
             TypePathType::Any => self.build_default(),
             TypePathType::OptionalNode => {
-                let name = &self.visitor_field_name;
-                let ty = &self.visitor_field_type;
                 quote! {
-                    let #name = match <#ty as ::async_xml::reader::Visitor<B>>::build(self.#name) {
+                    let #name = match #build_inner {
                         Ok(#name) => Some(#name),
                         Err(e) => {
                             ::tracing::trace!("discarding flattened build error: {:?}", e);
@@ -285,10 +876,8 @@ impl<'a> FieldData<'a> {
                 }
             }
             TypePathType::XmlNode => {
-                let name = &self.visitor_field_name;
-                let ty = &self.visitor_field_type;
                 quote! {
-                    let #name = <#ty as ::async_xml::reader::Visitor<B>>::build(self.#name)?;
+                    let #name = #build_inner?;
                 }
             }
         }
@@ -300,17 +889,69 @@ impl<'a> FieldData<'a> {
             TypePathType::Any => {
                 quote! { #name.into() }
             }
-            TypePathType::Vec | TypePathType::Option => {
+            TypePathType::Vec
+            | TypePathType::OptionalVec
+            | TypePathType::Map
+            | TypePathType::Option => {
                 quote! { self.#name }
             }
             TypePathType::XmlNode | TypePathType::OptionalNode => {
                 quote! { #name }
             }
         };
+        let val = match (&self.attrs.from, self.type_type) {
+            // convert the deserialized value, just like the container-level try_from
+            (attr::From::TryFrom(_), TypePathType::Any) => {
+                let convert = self.try_convert(quote!(#name), self.child_name().as_ref());
+                quote! { #convert? }
+            }
+            (attr::From::TryFrom(_), TypePathType::Option) => {
+                let convert = self.try_convert(quote!(val), self.child_name().as_ref());
+                quote! { self.#name.map(|val| #convert).transpose()? }
+            }
+            // collection items have already been converted when collected
+            _ => val,
+        };
         match struct_type {
             StructType::Normal => quote! { #name: #val, },
             StructType::Newtype | StructType::Tuple => quote! { #val, },
+            StructType::Unit => unreachable!("unit structs don't have fields"),
+        }
+    }
+
+    /// expression converting `val` into this field's type using [`TryFrom`], returning a `Result`
+    ///
+    /// Errors converting child elements are wrapped with the given child name, like other errors of the child.
+    fn try_convert(&self, val: TokenStream, child: Option<&TokenStream>) -> TokenStream {
+        let error = quote! {
+            ::async_xml::Error::Deserialization(format!("error converting: {}", e))
+        };
+        let error = match child {
+            Some(child) => quote! {
+                ::async_xml::Error::InnerDeserialiaztionError(#child.into(), Box::new(#error))
+            },
+            None => error,
+        };
+        quote! {
+            ::core::convert::TryFrom::try_from(#val).map_err(|e| #error)
+        }
+    }
+
+    /// name of the child element this field is deserialized from, used in errors
+    fn child_name(&self) -> Option<TokenStream> {
+        if self.attrs.source != FieldSource::Child {
+            return None;
         }
+        Some(match self.position {
+            Some(position) => {
+                let child = format!("child #{}", position + 1);
+                quote! { #child }
+            }
+            None => {
+                let tag = &self.tag_name;
+                quote! { #tag }
+            }
+        })
     }
 
     fn build_default(&self) -> TokenStream {
@@ -336,15 +977,75 @@ impl<'a> FieldData<'a> {
         }
     }
 
+    /// statements checking the number of elements collected into this field against `min_occurs` and `max_occurs`
+    fn build_occurs(&self) -> TokenStream {
+        let name = &self.visitor_field_name;
+        // wrapped collections count their items, not the wrapping element
+        let tag = match &self.attrs.wrapped {
+            Some(item) => quote! { #item },
+            None => {
+                let tag = &self.tag_name;
+                quote! { #tag }
+            }
+        };
+        let violated = match (
+            self.attrs.min_occurs.filter(|min| *min > 0),
+            self.attrs.max_occurs,
+        ) {
+            (Some(min), Some(max)) => quote! { !(#min..=#max).contains(&count) },
+            (Some(min), None) => quote! { count < #min },
+            (None, Some(max)) => quote! { count > #max },
+            (None, None) => return TokenStream::new(),
+        };
+        let min = self.attrs.min_occurs.unwrap_or(0);
+        let max = match self.attrs.max_occurs {
+            Some(max) => quote! { Some(#max) },
+            None => quote! { None },
+        };
+        let check = quote! {
+            let count = ::core::iter::IntoIterator::into_iter(collection).count();
+            if #violated {
+                return Err(::async_xml::Error::UnexpectedOccurrences(#tag.into(), count, #min, #max));
+            }
+        };
+        if self.type_type == TypePathType::OptionalVec {
+            // an absent optional collection isn't checked
+            quote! {
+                if let Some(collection) = &self.#name {
+                    #check
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let collection = &self.#name;
+                    #check
+                }
+            }
+        }
+    }
+
     fn build_error(&self) -> TokenStream {
         let tag = &self.tag_name;
         match self.attrs.source {
             FieldSource::Attribute => quote! {::async_xml::Error::MissingAttribute(#tag.into())},
-            FieldSource::Child => quote! {::async_xml::Error::MissingChild(#tag.into())},
+            FieldSource::Child => {
+                let child = self.child_name();
+                quote! {::async_xml::Error::MissingChild(#child.into())}
+            }
             FieldSource::Value => quote! {::async_xml::Error::MissingText},
-            FieldSource::Remains | FieldSource::Flatten => {
-                unreachable!("remains/flatten cannot fail")
+            FieldSource::Tag => quote! {::async_xml::Error::MissingStart},
+            FieldSource::Remains | FieldSource::Flatten | FieldSource::OtherAttributes => {
+                unreachable!("remains/flatten/other attributes cannot fail")
             }
         }
     }
 }
+
+/// check a nested path like `a/b/c` or `a/b/@c` for empty segments and misplaced attribute selectors
+fn is_valid_path(path: &str) -> bool {
+    let segments = path.split('/').collect::<Vec<_>>();
+    let (last, parents) = segments.split_last().unwrap();
+    let last = last.strip_prefix('@').unwrap_or(last);
+    !last.is_empty() && parents.iter().all(|s| !s.is_empty() && !s.starts_with('@'))
+}
@@ -6,45 +6,48 @@ use quote::quote;
 pub fn expand_from_xml(input: &syn::DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
     let ctx = Ctx::new();
     let container = Container::from_attrs(&ctx, &input.attrs);
+    for param in input.generics.type_params() {
+        if param.ident == "B" {
+            ctx.error_spanned_by(param, "type parameter B is reserved for the reader");
+        }
+    }
     ctx.check()?;
 
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    // conversions only need the bounds given by the type itself
+    let generics = crate::bound::with_reader(&input.generics, container.bound.as_deref(), false);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
     match &container.from {
         attr::From::Default => {}
         attr::From::From(t) => {
-            let name = &input.ident;
             return Ok(quote! {
-                impl<B> ::async_xml::reader::FromXml<B> for #name
-                where
-                    B: ::tokio::io::AsyncBufRead + Unpin,
-                {
-                    type Visitor = ::async_xml::reader::FromVisitor<B, #name, #t>;
+                impl #impl_generics ::async_xml::reader::FromXml<B> for #name #ty_generics #where_clause {
+                    type Visitor = ::async_xml::reader::FromVisitor<B, #name #ty_generics, #t>;
                 }
             });
         }
         attr::From::TryFrom(t) => {
-            let name = &input.ident;
             return Ok(quote! {
-                impl<B> ::async_xml::reader::FromXml<B> for #name
-                where
-                    B: ::tokio::io::AsyncBufRead + Unpin,
-                {
-                    type Visitor = ::async_xml::reader::TryFromVisitor<B, #name, #t, <#name as ::core::convert::TryFrom<#t>>::Error>;
+                impl #impl_generics ::async_xml::reader::FromXml<B> for #name #ty_generics #where_clause {
+                    type Visitor = ::async_xml::reader::TryFromVisitor<B, #name #ty_generics, #t, <#name #ty_generics as ::core::convert::TryFrom<#t>>::Error>;
                 }
             });
         }
         attr::From::FromStr => {
-            let name = &input.ident;
+            let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
             return Ok(quote! {
-                impl ::async_xml::reader::XmlFromStr for #name {}
+                impl #impl_generics ::async_xml::reader::XmlFromStr for #name #ty_generics #where_clause {}
             });
         }
     }
 
     match &input.data {
-        syn::Data::Struct(d) => crate::xml_struct::expand_struct(container, &*input, d),
-        _ => Err(vec![syn::Error::new_spanned(
+        syn::Data::Struct(d) => crate::xml_struct::expand_struct(container, input, d),
+        syn::Data::Enum(d) => crate::xml_enum::expand_enum(container, input, d),
+        syn::Data::Union(_) => Err(vec![syn::Error::new_spanned(
             input,
-            "only struct types implemented",
+            "union types can't be deserialized",
         )]),
     }
 }
@@ -3,12 +3,15 @@ use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
 mod attr;
+mod bound;
+mod case;
 mod ctx;
 mod field;
 mod from_xml;
 mod path;
 mod respan;
 mod symbol;
+mod xml_enum;
 mod xml_struct;
 
 #[proc_macro_derive(FromXml, attributes(async_xml))]
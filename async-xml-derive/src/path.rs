@@ -4,43 +4,76 @@ use crate::symbol::*;
 pub enum TypePathType {
     Any,
     Vec,
+    OptionalVec,
+    Map,
     Option,
     XmlNode,
     OptionalNode,
 }
 
+/// Collection types collecting repeated child elements through `XmlCollection`
+const COLLECTIONS: &[Symbol] = &[
+    VEC,
+    VEC_DEQUE,
+    LINKED_LIST,
+    BTREE_SET,
+    HASH_SET,
+    BINARY_HEAP,
+    SMALL_VEC,
+];
+
+/// Map types collecting child elements by key through `XmlMap`
+const MAPS: &[Symbol] = &[HASH_MAP, BTREE_MAP, INDEX_MAP];
+
+/// Pointer types wrapping deserialized values through `XmlWrapper`
+const WRAPPERS: &[Symbol] = &[BOX, RC, ARC];
+
+/// Get the last segment of a type path, so qualified paths like `std::vec::Vec<T>` resolve like `Vec<T>`
+fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path.path.segments.last(),
+        _ => None,
+    }
+}
+
 pub fn get_type_path_type(ty: &syn::Type) -> TypePathType {
-    if let syn::Type::Path(path) = ty {
-        if path.path.segments.len() == 1 && path.path.leading_colon.is_none() {
-            let segment = &path.path.segments[0];
-            if segment.ident == VEC {
-                return TypePathType::Vec;
-            } else if segment.ident == OPTION {
-                return TypePathType::Option;
-            } else if segment.ident == XML_NODE {
-                // TODO: make this more stable for paths like async_xml::util::XmlNode
-                return TypePathType::XmlNode;
-            }
+    if let Some(segment) = last_segment(ty) {
+        if COLLECTIONS.iter().any(|c| segment.ident == *c) {
+            return TypePathType::Vec;
+        } else if MAPS.iter().any(|m| segment.ident == *m) {
+            return TypePathType::Map;
+        } else if segment.ident == OPTION {
+            return TypePathType::Option;
+        } else if segment.ident == XML_NODE {
+            return TypePathType::XmlNode;
         }
     }
     TypePathType::Any
 }
 
-pub fn get_generic_arg(ty: &syn::Type) -> syn::Type {
-    if let syn::Type::Path(path) = ty {
-        if path.path.segments.len() == 1 && path.path.leading_colon.is_none() {
-            let segment = &path.path.segments[0];
-            if segment.ident == VEC || segment.ident == OPTION {
-                match &segment.arguments {
-                    syn::PathArguments::AngleBracketed(a) => {
-                        if let syn::GenericArgument::Type(t) = &a.args[0] {
-                            return t.clone();
-                        }
-                    }
-                    _ => (),
-                }
+/// Get the first generic type argument of a type, if any
+pub fn generic_arg(ty: &syn::Type) -> Option<syn::Type> {
+    if let syn::PathArguments::AngleBracketed(a) = &last_segment(ty)?.arguments {
+        for arg in &a.args {
+            if let syn::GenericArgument::Type(t) = arg {
+                return Some(t.clone());
             }
         }
     }
-    panic!("not a vector type!");
+    None
+}
+
+pub fn get_generic_arg(ty: &syn::Type) -> syn::Type {
+    generic_arg(ty).expect("not a generic type!")
+}
+
+/// Check whether a type is a pointer type like `Box<T>` or `Arc<str>`
+pub fn is_wrapper(ty: &syn::Type) -> bool {
+    match last_segment(ty) {
+        Some(segment) => {
+            WRAPPERS.iter().any(|w| segment.ident == *w)
+                && matches!(segment.arguments, syn::PathArguments::AngleBracketed(_))
+        }
+        None => false,
+    }
 }
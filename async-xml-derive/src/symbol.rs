@@ -8,12 +8,26 @@ pub const ASYNC_XML: Symbol = Symbol("async_xml");
 pub const ATTRIBUTE: Symbol = Symbol("attribute");
 pub const VALUE: Symbol = Symbol("value");
 pub const CHILD: Symbol = Symbol("child");
+pub const TAG: Symbol = Symbol("tag");
+pub const OTHER_ATTRIBUTES: Symbol = Symbol("other_attributes");
 pub const REMAINS: Symbol = Symbol("remains");
 pub const DEFAULT: Symbol = Symbol("default");
 pub const RENAME: Symbol = Symbol("rename");
 pub const VEC: Symbol = Symbol("Vec");
 pub const OPTION: Symbol = Symbol("Option");
 pub const XML_NODE: Symbol = Symbol("XmlNode");
+pub const VEC_DEQUE: Symbol = Symbol("VecDeque");
+pub const LINKED_LIST: Symbol = Symbol("LinkedList");
+pub const BTREE_SET: Symbol = Symbol("BTreeSet");
+pub const HASH_SET: Symbol = Symbol("HashSet");
+pub const BINARY_HEAP: Symbol = Symbol("BinaryHeap");
+pub const SMALL_VEC: Symbol = Symbol("SmallVec");
+pub const HASH_MAP: Symbol = Symbol("HashMap");
+pub const BTREE_MAP: Symbol = Symbol("BTreeMap");
+pub const INDEX_MAP: Symbol = Symbol("IndexMap");
+pub const BOX: Symbol = Symbol("Box");
+pub const RC: Symbol = Symbol("Rc");
+pub const ARC: Symbol = Symbol("Arc");
 pub const FROM_STR: Symbol = Symbol("from_str");
 pub const FROM: Symbol = Symbol("from");
 pub const TRY_FROM: Symbol = Symbol("try_from");
@@ -22,6 +36,28 @@ pub const ALLOW_UNKNOWN_ATTRIBUTES: Symbol = Symbol("allow_unknown_attributes");
 pub const ALLOW_UNKNOWN_TEXT: Symbol = Symbol("allow_unknown_text");
 pub const ALLOW_UNKNOWN: Symbol = Symbol("allow_unknown");
 pub const FLATTEN: Symbol = Symbol("flatten");
+pub const SEED: Symbol = Symbol("seed");
+pub const DESERIALIZE_WITH: Symbol = Symbol("deserialize_with");
+pub const WITH: Symbol = Symbol("with");
+pub const FINISH: Symbol = Symbol("finish");
+pub const TAG_ATTRIBUTE: Symbol = Symbol("tag_attribute");
+pub const ALIAS: Symbol = Symbol("alias");
+pub const OTHER: Symbol = Symbol("other");
+pub const CASE_INSENSITIVE: Symbol = Symbol("case_insensitive");
+pub const ORDERED: Symbol = Symbol("ordered");
+pub const UNTAGGED: Symbol = Symbol("untagged");
+pub const TEXT: Symbol = Symbol("text");
+pub const XSI_TYPE: Symbol = Symbol("xsi_type");
+pub const BOUND: Symbol = Symbol("bound");
+pub const RENAME_ALL: Symbol = Symbol("rename_all");
+pub const RENAME_ALL_ATTRIBUTES: Symbol = Symbol("rename_all_attributes");
+pub const COLLECTION: Symbol = Symbol("collection");
+pub const KEY: Symbol = Symbol("key");
+pub const CHILD_ATTRIBUTE: Symbol = Symbol("child_attribute");
+pub const MIN_OCCURS: Symbol = Symbol("min_occurs");
+pub const MAX_OCCURS: Symbol = Symbol("max_occurs");
+pub const WRAPPED: Symbol = Symbol("wrapped");
+pub const DENY_DUPLICATE_KEYS: Symbol = Symbol("deny_duplicate_keys");
 
 impl PartialEq<Symbol> for Path {
     fn eq(&self, other: &Symbol) -> bool {
@@ -0,0 +1,824 @@
+use crate::{attr::Variant, ctx::Ctx, xml_struct::StructContainer};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, TokenStreamExt};
+
+pub enum VariantKind<'a> {
+    /// A tuple variant with exactly one field, deserialized using the field type's visitor
+    Newtype(&'a syn::Type),
+    /// Any other variant, deserialized using a generated visitor
+    Struct(Ident),
+}
+
+pub struct VariantData<'a> {
+    /// source variant
+    pub inner: &'a syn::Variant,
+    /// attributes on the source variant
+    pub attrs: Variant,
+    /// name selecting this variant, either an element name, an attribute value or the text of the element
+    pub tag_name: String,
+    pub kind: VariantKind<'a>,
+}
+
+impl<'a> VariantData<'a> {
+    pub fn from_variant(
+        ctx: &Ctx,
+        container: &crate::attr::Container,
+        enum_name: &Ident,
+        variant: &'a syn::Variant,
+    ) -> Self {
+        let attrs = Variant::from_attrs(ctx, &variant.attrs);
+        let tag_name = attrs.rename.clone().unwrap_or_else(|| {
+            let name = variant.ident.to_string();
+            match container.rename_all {
+                Some(rule) => rule.apply_to_variant(&name),
+                None => name,
+            }
+        });
+        let kind = match &variant.fields {
+            syn::Fields::Unnamed(f) if f.unnamed.len() == 1 => {
+                VariantKind::Newtype(&f.unnamed[0].ty)
+            }
+            _ => VariantKind::Struct(Ident::new(
+                &format!("__{}{}Visitor", enum_name, variant.ident),
+                Span::call_site(),
+            )),
+        };
+        Self {
+            inner: variant,
+            attrs,
+            tag_name,
+            kind,
+        }
+    }
+
+    /// element names accepted by the visitor of this variant, if it's selected by element name
+    fn tag_names(&self, select_by_element: bool) -> Vec<String> {
+        if select_by_element {
+            std::iter::once(&self.tag_name)
+                .chain(&self.attrs.alias)
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// match pattern for all names selecting this variant
+    fn pattern(&self) -> TokenStream {
+        let tag = &self.tag_name;
+        let aliases = &self.attrs.alias;
+        quote! { #tag #(| #aliases)* }
+    }
+
+    /// type of the visitor deserializing this variant from the given reader type
+    fn visitor_type(&self, reader: TokenStream) -> TokenStream {
+        match &self.kind {
+            VariantKind::Newtype(ty) => {
+                quote! { <#ty as ::async_xml::reader::FromXml<#reader>>::Visitor }
+            }
+            VariantKind::Struct(visitor_name) => quote! { #visitor_name<#reader> },
+        }
+    }
+
+    /// function converting the output of this variant's visitor into the enum
+    fn wrap_output(&self, enum_name: &Ident) -> TokenStream {
+        let ident = &self.inner.ident;
+        match &self.kind {
+            VariantKind::Newtype(_) => quote! { #enum_name::#ident },
+            VariantKind::Struct(_) => quote! { ::core::convert::identity },
+        }
+    }
+}
+
+/// tokens for the container's handling of unknown attributes, text and children
+struct Unknown {
+    attr: TokenStream,
+    text: TokenStream,
+    child: TokenStream,
+}
+
+impl Unknown {
+    fn new(container: &crate::attr::Container) -> Self {
+        Self {
+            attr: if container.allow_unknown_attributes {
+                quote! { Ok(()) }
+            } else {
+                quote! {
+                    if ::async_xml::util::is_reserved_attribute(name) {
+                        Ok(())
+                    } else {
+                        Err(::async_xml::Error::UnexpectedAttribute(name.into()))
+                    }
+                }
+            },
+            text: if container.allow_unknown_text {
+                quote! { Ok(()) }
+            } else {
+                quote! { Err(::async_xml::Error::UnexpectedText) }
+            },
+            child: if container.allow_unknown_children {
+                quote! { reader.skip_element().await }
+            } else {
+                quote! { Err(::async_xml::Error::UnexpectedChild(name.into())) }
+            },
+        }
+    }
+}
+
+pub fn expand_enum(
+    container: crate::attr::Container,
+    input: &syn::DeriveInput,
+    data: &syn::DataEnum,
+) -> Result<TokenStream, Vec<syn::Error>> {
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    let ctx = Ctx::new();
+    let variants = data
+        .variants
+        .iter()
+        .map(|v| VariantData::from_variant(&ctx, &container, name, v))
+        .collect::<Vec<_>>();
+    if variants.is_empty() {
+        ctx.error_spanned_by(input, "enums without variants can't be deserialized");
+    }
+    if !input.generics.params.is_empty() {
+        ctx.error_spanned_by(&input.generics, "generic enums can't be deserialized");
+    }
+    // unit variants are element names by default, text selects them by the element's text instead
+    let text_variants = container.text;
+    for variant in &variants {
+        if text_variants
+            && !variant.attrs.other
+            && !matches!(variant.inner.fields, syn::Fields::Unit)
+        {
+            ctx.error_spanned_by(
+                variant.inner,
+                "text enums can only have unit variants and an other variant",
+            );
+        }
+        if variant.attrs.other && !text_variants {
+            ctx.error_spanned_by(variant.inner, "other is only valid for text enums");
+        }
+        if variant.attrs.other && matches!(variant.inner.fields, syn::Fields::Named(_)) {
+            ctx.error_spanned_by(
+                variant.inner,
+                "other variant must be a unit or newtype variant",
+            );
+        }
+        if variant.attrs.case_insensitive && !text_variants {
+            ctx.error_spanned_by(
+                variant.inner,
+                "case_insensitive is only valid for text enums",
+            );
+        }
+    }
+    if variants.iter().filter(|v| v.attrs.other).count() > 1 {
+        ctx.error_spanned_by(input, "multiple other variants");
+    }
+    if container.case_insensitive && !text_variants {
+        ctx.error_spanned_by(input, "case_insensitive is only valid for text enums");
+    }
+    ctx.check()?;
+
+    let visitor_name = Ident::new(&format!("__{}Visitor", name), Span::call_site());
+    if text_variants {
+        let visitor = expand_text(&container, input, &variants, &visitor_name);
+        return Ok(quote! {
+            #visitor
+
+            impl<B: ::tokio::io::AsyncBufRead + Unpin> ::async_xml::reader::FromXml<B> for #name {
+                type Visitor = #visitor_name<B>;
+            }
+        });
+    }
+
+    // visitors for struct-like variants don't inherit the finish hook, which is run on the enum instead
+    let variant_container = crate::attr::Container {
+        finish: None,
+        ..container.clone()
+    };
+    // variants are only selected by element name if there's no other tag
+    let select_by_element =
+        container.tag_attribute.is_none() && !container.xsi_type && !container.untagged;
+    let mut variant_visitors = TokenStream::new();
+    for variant in &variants {
+        if let VariantKind::Struct(variant_visitor_name) = &variant.kind {
+            let ident = &variant.inner.ident;
+            let variant_struct = StructContainer::new(
+                &variant_container,
+                name.clone(),
+                &input.generics,
+                quote!(#name::#ident),
+                variant_visitor_name.clone(),
+                &variant.tag_names(select_by_element),
+                &variant.inner.fields,
+            )?;
+            variant_visitors.append_all(variant_struct.expand_visitor(vis));
+        }
+    }
+
+    let visitor = if let Some(tag_attribute) = &container.tag_attribute {
+        expand_tag_attribute(
+            &container,
+            input,
+            &variants,
+            &visitor_name,
+            Discriminator::Attribute(tag_attribute.value()),
+        )
+    } else if container.xsi_type {
+        expand_tag_attribute(
+            &container,
+            input,
+            &variants,
+            &visitor_name,
+            Discriminator::XsiType,
+        )
+    } else if container.untagged {
+        expand_untagged(&container, input, &variants, &visitor_name)
+    } else {
+        expand_choice(&container, input, &variants, &visitor_name)
+    };
+
+    Ok(quote! {
+        #variant_visitors
+        #visitor
+
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> ::async_xml::reader::FromXml<B> for #name {
+            type Visitor = #visitor_name<B>;
+        }
+    })
+}
+
+fn start_name(container: &crate::attr::Container) -> TokenStream {
+    if let Some(tag_name) = &container.tag_name {
+        quote!(Some(#tag_name))
+    } else {
+        quote!(None)
+    }
+}
+
+fn start_names(container: &crate::attr::Container) -> TokenStream {
+    let tag_names = container.tag_names();
+    quote!(vec![#(#tag_names),*])
+}
+
+fn finish_value(container: &crate::attr::Container) -> TokenStream {
+    if let Some(finish) = &container.finish {
+        quote! { #finish(value).await }
+    } else {
+        quote! { Ok(value) }
+    }
+}
+
+fn visitor_doc(name: &Ident) -> String {
+    format!(
+        "A [`Visitor`](::async_xml::Visitor) for deserializing a [`{}`]",
+        name
+    )
+}
+
+/// expand the visitor for enums whose variant is selected by the name of the single child element
+fn expand_choice(
+    container: &crate::attr::Container,
+    input: &syn::DeriveInput,
+    variants: &[VariantData],
+    visitor_name: &Ident,
+) -> TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    let mut visit_child_match = TokenStream::new();
+    for variant in variants {
+        let tag = &variant.tag_name;
+        let visitor_type = variant.visitor_type(quote!(B));
+        let wrap = variant.wrap_output(name);
+        let pattern = variant.pattern();
+        visit_child_match.append_all(quote! {
+            #pattern => {
+                if self.value.is_some() {
+                    return Err(::async_xml::Error::DoubleChild(name.into()));
+                }
+                let value = reader
+                    .deserialize_with_visitor(<#visitor_type>::default())
+                    .await
+                    .map_err(|e| {
+                        ::async_xml::Error::InnerDeserialiaztionError(#tag.into(), Box::new(e))
+                    })?;
+                self.value = Some(#wrap(value));
+                Ok(())
+            }
+        });
+    }
+    let choices = variants
+        .iter()
+        .map(|v| v.tag_name.as_str())
+        .collect::<Vec<_>>()
+        .join("|");
+
+    let start_name = start_name(container);
+    let start_names = start_names(container);
+    let finish = finish_value(container);
+    let Unknown {
+        attr: unknown_attr,
+        text: unknown_text,
+        child: unknown_child,
+    } = Unknown::new(container);
+    let visitor_doc = visitor_doc(name);
+
+    quote! {
+        #[doc = #visitor_doc]
+        #[doc(hidden)]
+        #vis struct #visitor_name<B> where B: tokio::io::AsyncBufRead + Unpin {
+            value: Option<#name>,
+            _phantom: core::marker::PhantomData<B>,
+        }
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> Default for #visitor_name<B> {
+            fn default() -> Self {
+                Self {
+                    value: None,
+                    _phantom: core::marker::PhantomData,
+                }
+            }
+        }
+
+        #[async_trait::async_trait(?Send)]
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> ::async_xml::Visitor<B> for #visitor_name<B> {
+            type Output = #name;
+
+            fn start_name() -> Option<&'static str> {
+                #start_name
+            }
+
+            fn start_names() -> Vec<&'static str> {
+                #start_names
+            }
+
+            #[allow(unused_variables)]
+            fn visit_attribute(&mut self, name: &str, value: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                #unknown_attr
+            }
+
+            #[allow(unused_variables)]
+            fn visit_text(&mut self, text: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                #unknown_text
+            }
+
+            async fn visit_child(
+                &mut self,
+                name: &str,
+                reader: &mut ::async_xml::reader::PeekingReader<B>,
+            ) -> ::core::result::Result<(), ::async_xml::Error> {
+                match name {
+                    #visit_child_match
+                    _ => #unknown_child,
+                }
+            }
+
+            fn build(self) -> ::core::result::Result<#name, ::async_xml::Error> {
+                self.value.ok_or_else(|| ::async_xml::Error::MissingChild(#choices.into()))
+            }
+
+            async fn finish(self) -> ::core::result::Result<#name, ::async_xml::Error> {
+                let value = <Self as ::async_xml::Visitor<B>>::build(self)?;
+                #finish
+            }
+        }
+    }
+}
+
+/// attribute selecting the variant of an enum
+enum Discriminator {
+    /// attribute with a fixed name
+    Attribute(String),
+    /// `xsi:type` attribute, whose value is a qualified name
+    XsiType,
+}
+
+/// expand the visitor for enums whose variant is selected by the value of an attribute
+///
+/// Until the attribute is visited, the tag name and all other attributes are buffered. Afterwards, they are replayed
+/// to the selected variant's visitor, which then receives all further events. The variant of `xsi:type` is only
+/// selected once all attributes have been visited, using the value the reader expanded to `{namespace}local`.
+fn expand_tag_attribute(
+    container: &crate::attr::Container,
+    input: &syn::DeriveInput,
+    variants: &[VariantData],
+    visitor_name: &Ident,
+    discriminator: Discriminator,
+) -> TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let variants_name = Ident::new(&format!("__{}Variants", name), Span::call_site());
+
+    let mut variant_defs = TokenStream::new();
+    let mut select_match = TokenStream::new();
+    let mut visit_tag_match = TokenStream::new();
+    let mut visit_attr_match = TokenStream::new();
+    let mut visit_text_match = TokenStream::new();
+    let mut visit_child_match = TokenStream::new();
+    let mut build_match = TokenStream::new();
+    let mut finish_match = TokenStream::new();
+    for variant in variants {
+        let ident = &variant.inner.ident;
+        let pattern = variant.pattern();
+        let visitor_type = variant.visitor_type(quote!(B));
+        let wrap = variant.wrap_output(name);
+        variant_defs.append_all(quote! { #ident(#visitor_type), });
+        select_match.append_all(quote! {
+            #pattern => Some(#variants_name::#ident(<#visitor_type>::default())),
+        });
+        visit_tag_match.append_all(quote! {
+            #variants_name::#ident(v) => <#visitor_type as ::async_xml::Visitor<B>>::visit_tag(v, name),
+        });
+        visit_attr_match.append_all(quote! {
+            #variants_name::#ident(v) => <#visitor_type as ::async_xml::Visitor<B>>::visit_attribute(v, name, value),
+        });
+        visit_text_match.append_all(quote! {
+            #variants_name::#ident(v) => <#visitor_type as ::async_xml::Visitor<B>>::visit_text(v, text),
+        });
+        visit_child_match.append_all(quote! {
+            #variants_name::#ident(v) => <#visitor_type as ::async_xml::Visitor<B>>::visit_child(v, name, reader).await,
+        });
+        build_match.append_all(quote! {
+            #variants_name::#ident(v) => <#visitor_type as ::async_xml::Visitor<B>>::build(v).map(#wrap),
+        });
+        finish_match.append_all(quote! {
+            #variants_name::#ident(v) => <#visitor_type as ::async_xml::Visitor<B>>::finish(v).await.map(#wrap),
+        });
+    }
+
+    let (select_variant, visit_discriminator, select_missing) = match &discriminator {
+        Discriminator::Attribute(tag_attribute) => (
+            quote! {
+                let variant = match value {
+                    #select_match
+                    _ => None,
+                };
+            },
+            quote! {
+                if name == #tag_attribute {
+                    return self.select(value);
+                }
+            },
+            quote! {
+                return Err(::async_xml::Error::MissingAttribute(#tag_attribute.into()));
+            },
+        ),
+        Discriminator::XsiType => (
+            quote! {
+                // the reader expands the type to `{namespace}local`, which is preferred over the local name
+                let (namespace, local) = ::async_xml::util::split_expanded_name(value);
+                let variant = namespace
+                    .map(|_| value)
+                    .into_iter()
+                    .chain(::core::iter::once(local))
+                    .find_map(|value| match value {
+                        #select_match
+                        _ => None,
+                    });
+            },
+            TokenStream::new(),
+            quote! {
+                let (name, value) = ::async_xml::util::xsi_type(&self.attributes)
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .ok_or_else(|| ::async_xml::Error::MissingAttribute("xsi:type".into()))?;
+                // like other tag attributes, the type isn't passed on to the variant
+                self.attributes.retain(|(n, _)| *n != name);
+                self.select(&value)?;
+            },
+        ),
+    };
+
+    let start_name = start_name(container);
+    let start_names = start_names(container);
+    let finish = finish_value(container);
+    let visitor_doc = visitor_doc(name);
+
+    quote! {
+        #[doc(hidden)]
+        #vis enum #variants_name<B> where B: tokio::io::AsyncBufRead + Unpin {
+            #variant_defs
+        }
+
+        #[doc = #visitor_doc]
+        #[doc(hidden)]
+        #vis struct #visitor_name<B> where B: tokio::io::AsyncBufRead + Unpin {
+            tag: Option<String>,
+            attributes: Vec<(String, String)>,
+            variant: Option<#variants_name<B>>,
+        }
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> Default for #visitor_name<B> {
+            fn default() -> Self {
+                Self {
+                    tag: None,
+                    attributes: Vec::new(),
+                    variant: None,
+                }
+            }
+        }
+
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> #visitor_name<B> {
+            fn select(&mut self, value: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                ::tracing::trace!("selecting variant {:?}", value);
+                #select_variant
+                let mut variant = variant.ok_or_else(|| ::async_xml::Error::UnknownVariant(value.into()))?;
+                if let Some(name) = &self.tag {
+                    match &mut variant {
+                        #visit_tag_match
+                    }?;
+                }
+                for (name, value) in self.attributes.drain(..) {
+                    let (name, value) = (name.as_str(), value.as_str());
+                    match &mut variant {
+                        #visit_attr_match
+                    }?;
+                }
+                self.variant = Some(variant);
+                Ok(())
+            }
+
+            fn variant(&mut self) -> ::core::result::Result<&mut #variants_name<B>, ::async_xml::Error> {
+                if self.variant.is_none() {
+                    #select_missing
+                }
+                Ok(self.variant.as_mut().unwrap())
+            }
+        }
+
+        #[async_trait::async_trait(?Send)]
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> ::async_xml::Visitor<B> for #visitor_name<B> {
+            type Output = #name;
+
+            fn start_name() -> Option<&'static str> {
+                #start_name
+            }
+
+            fn start_names() -> Vec<&'static str> {
+                #start_names
+            }
+
+            fn visit_tag(&mut self, name: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                self.tag = Some(name.into());
+                Ok(())
+            }
+
+            fn visit_attribute(&mut self, name: &str, value: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                if let Some(variant) = &mut self.variant {
+                    return match variant {
+                        #visit_attr_match
+                    };
+                }
+                #visit_discriminator
+                self.attributes.push((name.into(), value.into()));
+                Ok(())
+            }
+
+            fn visit_text(&mut self, text: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                match self.variant()? {
+                    #visit_text_match
+                }
+            }
+
+            async fn visit_child(
+                &mut self,
+                name: &str,
+                reader: &mut ::async_xml::reader::PeekingReader<B>,
+            ) -> ::core::result::Result<(), ::async_xml::Error> {
+                match self.variant()? {
+                    #visit_child_match
+                }
+            }
+
+            fn build(mut self) -> ::core::result::Result<#name, ::async_xml::Error> {
+                self.variant()?;
+                match self.variant.unwrap() {
+                    #build_match
+                }
+            }
+
+            async fn finish(mut self) -> ::core::result::Result<#name, ::async_xml::Error> {
+                self.variant()?;
+                let value = match self.variant.unwrap() {
+                    #finish_match
+                }?;
+                #finish
+            }
+        }
+    }
+}
+
+/// expand the visitor for enums with only unit variants, selected by the text of the element
+fn expand_text(
+    container: &crate::attr::Container,
+    input: &syn::DeriveInput,
+    variants: &[VariantData],
+    visitor_name: &Ident,
+) -> TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    let mut select = TokenStream::new();
+    for variant in variants.iter().filter(|v| !v.attrs.other) {
+        let ident = &variant.inner.ident;
+        let names = std::iter::once(&variant.tag_name).chain(&variant.attrs.alias);
+        let conditions = if container.case_insensitive || variant.attrs.case_insensitive {
+            quote! { #(text.eq_ignore_ascii_case(#names))||* }
+        } else {
+            quote! { #(text == #names)||* }
+        };
+        select.append_all(quote! {
+            if #conditions {
+                #name::#ident
+            } else
+        });
+    }
+    let fallback = match variants.iter().find(|v| v.attrs.other) {
+        Some(VariantData {
+            inner,
+            kind: VariantKind::Newtype(_),
+            ..
+        }) => {
+            let ident = &inner.ident;
+            quote! { #name::#ident(text.into()) }
+        }
+        Some(VariantData { inner, .. }) => {
+            let ident = &inner.ident;
+            quote! { #name::#ident }
+        }
+        None => quote! { return Err(::async_xml::Error::UnknownVariant(text.into())) },
+    };
+
+    let start_name = start_name(container);
+    let start_names = start_names(container);
+    let finish = finish_value(container);
+    let Unknown {
+        attr: unknown_attr,
+        child: unknown_child,
+        ..
+    } = Unknown::new(container);
+    let visitor_doc = visitor_doc(name);
+
+    quote! {
+        #[doc = #visitor_doc]
+        #[doc(hidden)]
+        #vis struct #visitor_name<B> where B: tokio::io::AsyncBufRead + Unpin {
+            value: Option<#name>,
+            _phantom: core::marker::PhantomData<B>,
+        }
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> Default for #visitor_name<B> {
+            fn default() -> Self {
+                Self {
+                    value: None,
+                    _phantom: core::marker::PhantomData,
+                }
+            }
+        }
+
+        #[async_trait::async_trait(?Send)]
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> ::async_xml::Visitor<B> for #visitor_name<B> {
+            type Output = #name;
+
+            fn start_name() -> Option<&'static str> {
+                #start_name
+            }
+
+            fn start_names() -> Vec<&'static str> {
+                #start_names
+            }
+
+            #[allow(unused_variables)]
+            fn visit_attribute(&mut self, name: &str, value: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                #unknown_attr
+            }
+
+            fn visit_text(&mut self, text: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                if self.value.is_some() {
+                    return Err(::async_xml::Error::DoubleText);
+                }
+                self.value = Some(#select { #fallback });
+                Ok(())
+            }
+
+            async fn visit_child(
+                &mut self,
+                name: &str,
+                reader: &mut ::async_xml::reader::PeekingReader<B>,
+            ) -> ::core::result::Result<(), ::async_xml::Error> {
+                #unknown_child
+            }
+
+            fn build(self) -> ::core::result::Result<#name, ::async_xml::Error> {
+                self.value.ok_or(::async_xml::Error::MissingText)
+            }
+
+            async fn finish(self) -> ::core::result::Result<#name, ::async_xml::Error> {
+                let value = <Self as ::async_xml::Visitor<B>>::build(self)?;
+                #finish
+            }
+        }
+    }
+}
+
+/// expand the visitor for untagged enums, which try to deserialize the element as each variant in order
+fn expand_untagged(
+    container: &crate::attr::Container,
+    input: &syn::DeriveInput,
+    variants: &[VariantData],
+    visitor_name: &Ident,
+) -> TokenStream {
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    let mut try_build = TokenStream::new();
+    let mut try_finish = TokenStream::new();
+    for variant in variants {
+        let variant_name = variant.inner.ident.to_string();
+        let visitor_type = variant.visitor_type(quote!(::async_xml::util::ReplayReader));
+        let wrap = variant.wrap_output(name);
+        try_build.append_all(quote! {
+            match self.element.replay_sync(<#visitor_type>::default()) {
+                Ok(value) => return Ok(#wrap(value)),
+                Err(e) => errors.push((#variant_name.into(), e)),
+            }
+        });
+        try_finish.append_all(quote! {
+            match self.element.replay(<#visitor_type>::default()).await {
+                Ok(value) => return Ok(#wrap(value)),
+                Err(e) => errors.push((#variant_name.into(), e)),
+            }
+        });
+    }
+
+    let start_name = start_name(container);
+    let start_names = start_names(container);
+    let finish = finish_value(container);
+    let visitor_doc = visitor_doc(name);
+
+    quote! {
+        #[doc = #visitor_doc]
+        #[doc(hidden)]
+        #vis struct #visitor_name<B> where B: tokio::io::AsyncBufRead + Unpin {
+            element: ::async_xml::util::BufferedElement,
+            _phantom: core::marker::PhantomData<B>,
+        }
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> Default for #visitor_name<B> {
+            fn default() -> Self {
+                Self {
+                    element: Default::default(),
+                    _phantom: core::marker::PhantomData,
+                }
+            }
+        }
+
+        #[async_trait::async_trait(?Send)]
+        impl<B: ::tokio::io::AsyncBufRead + Unpin> ::async_xml::Visitor<B> for #visitor_name<B> {
+            type Output = #name;
+
+            fn start_name() -> Option<&'static str> {
+                #start_name
+            }
+
+            fn start_names() -> Vec<&'static str> {
+                #start_names
+            }
+
+            fn visit_tag(&mut self, name: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                <::async_xml::util::BufferedElement as ::async_xml::Visitor<B>>::visit_tag(&mut self.element, name)
+            }
+
+            fn visit_attribute(&mut self, name: &str, value: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                <::async_xml::util::BufferedElement as ::async_xml::Visitor<B>>::visit_attribute(&mut self.element, name, value)
+            }
+
+            fn visit_text(&mut self, text: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                <::async_xml::util::BufferedElement as ::async_xml::Visitor<B>>::visit_text(&mut self.element, text)
+            }
+
+            async fn visit_child(
+                &mut self,
+                name: &str,
+                reader: &mut ::async_xml::reader::PeekingReader<B>,
+            ) -> ::core::result::Result<(), ::async_xml::Error> {
+                <::async_xml::util::BufferedElement as ::async_xml::Visitor<B>>::visit_child(&mut self.element, name, reader).await
+            }
+
+            fn build(self) -> ::core::result::Result<#name, ::async_xml::Error> {
+                let mut errors = Vec::new();
+                #try_build
+                Err(::async_xml::Error::NoMatchingVariant(errors))
+            }
+
+            async fn finish(self) -> ::core::result::Result<#name, ::async_xml::Error> {
+                // the finish hook runs on whichever variant matched first
+                let value = async {
+                    let mut errors = Vec::new();
+                    #try_finish
+                    Err(::async_xml::Error::NoMatchingVariant(errors))
+                }
+                .await?;
+                #finish
+            }
+        }
+    }
+}
@@ -1,6 +1,12 @@
-use crate::{attr::FieldSource, ctx::Ctx, field::FieldData};
+use crate::{
+    attr::{FieldSource, MapKey},
+    ctx::Ctx,
+    field::FieldData,
+    path::TypePathType,
+};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, TokenStreamExt};
+use std::collections::BTreeSet;
 
 #[derive(Copy, Clone, PartialEq)]
 pub enum StructType {
@@ -10,42 +16,58 @@ pub enum StructType {
     Newtype,
     /// A tuple struct
     Tuple,
+    /// A struct without any fields
+    Unit,
 }
 
 pub struct StructContainer<'a> {
-    attr: crate::attr::Container,
-    /// Name of the input struct
+    attr: &'a crate::attr::Container,
+    /// Name of the output type
     name: Ident,
+    /// Output type including its generic arguments
+    output: TokenStream,
+    /// Generics of the output type extended by the reader type
+    generics: syn::Generics,
+    /// Path used to construct the output, e.g. the struct name or an enum variant
+    constructor: TokenStream,
     /// Name of the generated visitor
     visitor_name: Ident,
     /// Field data
     fields: Vec<FieldData<'a>>,
     /// Value for expected tag name
     tag_name: TokenStream,
+    /// Value for all accepted tag names
+    tag_names: TokenStream,
+    /// Intermediate elements of nested field paths
+    nested_paths: BTreeSet<String>,
     struct_type: StructType,
 }
 
 impl<'a> StructContainer<'a> {
     pub fn new(
-        container: crate::attr::Container,
-        input: &syn::DeriveInput,
-        data: &'a syn::DataStruct,
+        container: &'a crate::attr::Container,
+        name: Ident,
+        generics: &syn::Generics,
+        constructor: TokenStream,
+        visitor_name: Ident,
+        tag_names: &[String],
+        data: &'a syn::Fields,
     ) -> Result<Self, Vec<syn::Error>> {
-        let name = input.ident.clone();
-        let visitor_name = Ident::new(&format!("__{}Visitor", name), Span::call_site());
-
-        let tag_name = if let Some(tag_name) = &container.tag_name {
+        let (_, ty_generics, _) = generics.split_for_impl();
+        let output = quote!(#name #ty_generics);
+        let generics = crate::bound::with_reader(generics, container.bound.as_deref(), true);
+        let tag_name = if let Some(tag_name) = tag_names.first() {
             quote!(Some(#tag_name))
         } else {
             quote!(None)
         };
+        let tag_names = quote!(vec![#(#tag_names),*]);
 
         let ctx = Ctx::new();
         let mut fields = data
-            .fields
             .iter()
             .enumerate()
-            .flat_map(|(i, f)| FieldData::from_field(&ctx, f, i).ok())
+            .flat_map(|(i, f)| FieldData::from_field(&ctx, container, f, i).ok())
             .collect::<Vec<_>>();
         if fields
             .iter()
@@ -56,7 +78,24 @@ impl<'a> StructContainer<'a> {
             let mut errs = fields
                 .iter()
                 .filter(|f| f.attrs.source == FieldSource::Value)
-                .map(|f| syn::Error::new_spanned(&f.inner, "multiple fields sourcing from text"))
+                .map(|f| syn::Error::new_spanned(f.inner, "multiple fields sourcing from text"))
+                .collect::<Vec<_>>();
+            let mut err = errs.remove(0);
+            for e in errs {
+                err.combine(e);
+            }
+            ctx.syn_error(err);
+        }
+        let other_attributes_fields = fields
+            .iter()
+            .filter(|f| f.attrs.source == FieldSource::OtherAttributes)
+            .collect::<Vec<_>>();
+        if other_attributes_fields.len() > 1 {
+            let mut errs = other_attributes_fields
+                .iter()
+                .map(|f| {
+                    syn::Error::new_spanned(f.inner, "multiple fields collecting other attributes")
+                })
                 .collect::<Vec<_>>();
             let mut err = errs.remove(0);
             for e in errs {
@@ -72,7 +111,7 @@ impl<'a> StructContainer<'a> {
             let mut errs = fields
                 .iter()
                 .filter(|f| f.attrs.source == FieldSource::Remains)
-                .map(|f| syn::Error::new_spanned(&f.inner, "multiple fields sourcing from remains"))
+                .map(|f| syn::Error::new_spanned(f.inner, "multiple fields sourcing from remains"))
                 .collect::<Vec<_>>();
             let mut err = errs.remove(0);
             for e in errs {
@@ -80,8 +119,81 @@ impl<'a> StructContainer<'a> {
             }
             ctx.syn_error(err);
         }
+        if matches!(data, syn::Fields::Unnamed(f) if f.unnamed.len() > 1) {
+            let positional = fields.iter_mut().filter(|f| f.is_positional());
+            for (position, field) in positional.enumerate() {
+                if !matches!(field.type_type, TypePathType::Any | TypePathType::Option)
+                    || field.attrs.wrapped.is_some()
+                {
+                    ctx.error_spanned_by(
+                        field.inner,
+                        "positional child elements must be single values, use rename for collections",
+                    );
+                }
+                field.position = Some(position);
+            }
+        }
+        if container.ordered {
+            for (i, field) in fields.iter_mut().enumerate() {
+                if field.attrs.source == FieldSource::Child && field.path_prefixes().is_empty() {
+                    field.order = Some(i);
+                }
+            }
+        }
+        let mut nested_paths = fields
+            .iter()
+            .flat_map(|f| f.path_prefixes())
+            .collect::<BTreeSet<_>>();
+        let paths = fields
+            .iter()
+            .flat_map(|f| f.tag_names())
+            .filter(|p| p.contains('/'))
+            .collect::<Vec<_>>();
+        for field in &mut fields {
+            if field.attrs.source != FieldSource::Child {
+                continue;
+            }
+            let names = field
+                .tag_names()
+                .into_iter()
+                .filter(|name| nested_paths.contains(name))
+                .collect::<Vec<_>>();
+            if names.is_empty() {
+                continue;
+            }
+            // attributes directly below a child element can be passed back while deserializing the child
+            let mut valid = field.attrs.seed.is_none()
+                && field.attrs.deserialize_with.is_none()
+                && field.attrs.wrapped.is_none()
+                && !matches!(field.attrs.from, crate::attr::From::FromStr)
+                && field.type_type != TypePathType::Map;
+            let mut attributes = Vec::new();
+            for name in &names {
+                for path in &paths {
+                    if let Some(rest) = path.strip_prefix(&format!("{}/", name)) {
+                        match rest.strip_prefix('@') {
+                            Some(attribute) => attributes.push(attribute.to_string()),
+                            None => valid = false,
+                        }
+                    }
+                }
+            }
+            if !valid {
+                ctx.error_spanned_by(
+                    field.inner,
+                    "child element is also an intermediate element of a nested path",
+                );
+                continue;
+            }
+            for name in &names {
+                nested_paths.remove(name);
+            }
+            attributes.sort();
+            attributes.dedup();
+            field.child_attributes = attributes;
+        }
         ctx.check()?;
-        let struct_type = match &data.fields {
+        let struct_type = match data {
             syn::Fields::Named(_) => StructType::Normal,
             syn::Fields::Unnamed(f) => {
                 if f.unnamed.len() == 1 {
@@ -90,7 +202,7 @@ impl<'a> StructContainer<'a> {
                     StructType::Tuple
                 }
             }
-            syn::Fields::Unit => unreachable!("Unit variant invalid for structs"),
+            syn::Fields::Unit => StructType::Unit,
         };
         // make sure the remains field is last
         if remain_count == 1 {
@@ -107,9 +219,14 @@ impl<'a> StructContainer<'a> {
         Ok(Self {
             attr: container,
             name,
+            output,
+            generics,
+            constructor,
             visitor_name,
             fields,
             tag_name,
+            tag_names,
+            nested_paths,
             struct_type,
         })
     }
@@ -120,204 +237,312 @@ pub fn expand_struct(
     input: &syn::DeriveInput,
     data: &syn::DataStruct,
 ) -> Result<TokenStream, Vec<syn::Error>> {
-    let container = StructContainer::new(container, input, data)?;
-
-    let visitor_name = &container.visitor_name;
-    let name = &container.name;
-    let tag_name = &container.tag_name;
-    let vis = &input.vis;
-
-    let mut visitor_fields = TokenStream::new();
-    visitor_fields.append_all(
-        container
-            .fields
-            .iter()
-            .map(|f| f.visitor_struct_field_def()),
-    );
-    let mut visitor_default = TokenStream::new();
-    visitor_default.append_all(
-        container
-            .fields
-            .iter()
-            .map(|f| f.visitor_struct_default_field()),
-    );
-    let mut visitor_build_fields = TokenStream::new();
-    visitor_build_fields.append_all(
-        container
-            .fields
-            .iter()
-            .map(|f| f.visitor_build_field(&container.struct_type)),
-    );
-
-    let mut visitor_visit_attr_match = TokenStream::new();
-    let mut visitor_visit_attr_match_any = TokenStream::new();
-    let mut visitor_visit_child_match = TokenStream::new();
-    let mut visitor_visit_child_match_any = TokenStream::new();
-    let mut visitor_visit_value = TokenStream::new();
-    let mut visitor_visit_tag = TokenStream::new();
-    for field in &container.fields {
-        field.visitor_visit(
-            &mut visitor_visit_attr_match,
-            &mut visitor_visit_attr_match_any,
-            &mut visitor_visit_child_match,
-            &mut visitor_visit_child_match_any,
-            &mut visitor_visit_value,
-            &mut visitor_visit_tag,
-        );
+    if let Some(tag_attribute) = &container.tag_attribute {
+        return Err(vec![syn::Error::new_spanned(
+            tag_attribute,
+            "tag_attribute is only valid for enums",
+        )]);
     }
-    if container.attr.allow_unknown_text {
-        visitor_visit_value.append_all(quote! {
-            #[allow(unreachable_code)]
-            Ok(())
-        });
-    } else {
-        visitor_visit_value.append_all(quote! {
-            #[allow(unreachable_code)]
-            Err(::async_xml::Error::UnexpectedText)
-        });
+    if container.case_insensitive {
+        return Err(vec![syn::Error::new_spanned(
+            &input.ident,
+            "case_insensitive is only valid for enums",
+        )]);
+    }
+    if container.untagged {
+        return Err(vec![syn::Error::new_spanned(
+            &input.ident,
+            "untagged is only valid for enums",
+        )]);
+    }
+    if container.text {
+        return Err(vec![syn::Error::new_spanned(
+            &input.ident,
+            "text is only valid for enums",
+        )]);
+    }
+    if container.xsi_type {
+        return Err(vec![syn::Error::new_spanned(
+            &input.ident,
+            "xsi_type is only valid for enums",
+        )]);
     }
 
-    let mut visitor_build = TokenStream::new();
-    visitor_build.append_all(container.fields.iter().map(|f| f.visitor_build()));
+    let name = &input.ident;
+    let visitor_name = Ident::new(&format!("__{}Visitor", name), Span::call_site());
+    let struct_container = StructContainer::new(
+        &container,
+        name.clone(),
+        &input.generics,
+        quote!(#name),
+        visitor_name.clone(),
+        &container.tag_names(),
+        &data.fields,
+    )?;
+    let visitor = struct_container.expand_visitor(&input.vis);
+    let (impl_generics, visitor_ty_generics, where_clause) =
+        struct_container.generics.split_for_impl();
+    let output = &struct_container.output;
 
-    let visitor_doc = format!(
-        "A [`Visitor`](::async_xml::Visitor) for deserializing a [`{}`]",
-        name
-    );
-    let visitor = quote! {
-        #[doc = #visitor_doc]
-        #[doc(hidden)]
-        #vis struct #visitor_name<B> where B: tokio::io::AsyncBufRead + Unpin {
-            #visitor_fields
-            _phantom: core::marker::PhantomData<B>,
-        }
-        impl<B: ::tokio::io::AsyncBufRead + Unpin> Default for #visitor_name<B> {
-            fn default() -> Self {
-                Self {
-                    #visitor_default
-                    _phantom: core::marker::PhantomData,
-                }
-            }
-        }
-    };
-    let mut visitor_impl: syn::ItemImpl = syn::parse2(quote! {
-        #[async_trait::async_trait(?Send)]
-        impl<B: ::tokio::io::AsyncBufRead + Unpin> ::async_xml::Visitor<B> for #visitor_name<B> {
-            type Output = #name;
+    Ok(quote! {
+        #visitor
+        impl #impl_generics ::async_xml::reader::FromXml<B> for #output #where_clause {
+            type Visitor = #visitor_name #visitor_ty_generics;
         }
     })
-    .unwrap();
+}
 
-    let visitor_fn_build = match container.struct_type {
-        StructType::Normal => {
-            quote! {
-                fn build(self) -> ::core::result::Result<#name, ::async_xml::Error> {
-                    #visitor_build
+impl<'a> StructContainer<'a> {
+    /// Generate the visitor struct and its [`Visitor`] implementation
+    pub fn expand_visitor(&self, vis: &syn::Visibility) -> TokenStream {
+        let visitor_name = &self.visitor_name;
+        let name = &self.name;
+        let output = &self.output;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let constructor = &self.constructor;
+        let tag_name = &self.tag_name;
+        let tag_names = &self.tag_names;
 
-                    Ok(#name {
-                        #visitor_build_fields
-                    })
-                }
-            }
+        let mut visitor_fields = TokenStream::new();
+        visitor_fields.append_all(self.fields.iter().map(|f| f.visitor_struct_field_def()));
+        let mut visitor_default = TokenStream::new();
+        visitor_default.append_all(self.fields.iter().map(|f| f.visitor_struct_default_field()));
+        if self.fields.iter().any(|f| f.position.is_some()) {
+            // index of the next positional child element
+            visitor_fields.append_all(quote! { __position: usize, });
+            visitor_default.append_all(quote! { __position: 0, });
         }
-        StructType::Newtype | StructType::Tuple => {
-            quote! {
-                fn build(self) -> ::core::result::Result<#name, ::async_xml::Error> {
-                    #visitor_build
+        if self.attr.ordered {
+            // position of the last seen child element in the field order
+            visitor_fields.append_all(quote! { __order: usize, });
+            visitor_default.append_all(quote! { __order: 0, });
+        }
+        let mut visitor_build_fields = TokenStream::new();
+        visitor_build_fields.append_all(
+            self.fields
+                .iter()
+                .map(|f| f.visitor_build_field(&self.struct_type)),
+        );
 
-                    Ok(#name(
-                        #visitor_build_fields
-                    ))
+        let mut visitor_visit_attr_match = TokenStream::new();
+        let mut visitor_visit_attr_match_any = TokenStream::new();
+        let mut visitor_visit_child_match = TokenStream::new();
+        let mut visitor_visit_child_match_any = TokenStream::new();
+        let mut visitor_visit_value = TokenStream::new();
+        let mut visitor_visit_tag = TokenStream::new();
+        // named child elements take precedence over positional ones, and unknown attributes are offered to flattened
+        // fields before being collected, and to remains last. maps keyed by tag name take all unknown children left
+        // over by flattened fields, so remains only receive unknown attributes and text next to them
+        let mut visit_order = self.fields.iter().collect::<Vec<_>>();
+        visit_order.sort_by_key(|f| match f.attrs.source {
+            _ if f.position.is_some() => 1,
+            FieldSource::OtherAttributes => 1,
+            FieldSource::Child if matches!(f.attrs.key, Some(MapKey::Tag)) => 2,
+            FieldSource::Remains => 3,
+            _ => 0,
+        });
+        for field in visit_order {
+            field.visitor_visit(
+                &mut visitor_visit_attr_match,
+                &mut visitor_visit_attr_match_any,
+                &mut visitor_visit_child_match,
+                &mut visitor_visit_child_match_any,
+                &mut visitor_visit_value,
+                &mut visitor_visit_tag,
+            );
+        }
+        let allow_unknown_text = self.attr.allow_unknown_text;
+        for path in &self.nested_paths {
+            visitor_visit_child_match.append_all(quote! {
+                #path => {
+                    let visitor = ::async_xml::util::PathVisitor::new(self, #path, #allow_unknown_text);
+                    reader.deserialize_with_visitor(visitor).await?;
                 }
-            }
+            });
         }
-    };
-    visitor_impl
-        .items
-        .push(syn::parse2(visitor_fn_build).unwrap());
-    visitor_impl.items.push(
-        syn::parse2(quote! {
-            fn start_name() -> Option<&'static str> {
-                #tag_name
-            }
-        })
-        .unwrap(),
-    );
-    visitor_impl.items.push(
-        syn::parse2(quote! {
-            fn visit_tag(&mut self, name: &str) -> ::core::result::Result<(), ::async_xml::Error> {
-                #visitor_visit_tag
+        if !self.nested_paths.is_empty() {
+            // flattened fields and remains only receive the element's own attributes and children
+            visitor_visit_attr_match_any = quote! {
+                if !name.contains('/') {
+                    #visitor_visit_attr_match_any
+                }
+            };
+            visitor_visit_child_match_any = quote! {
+                if !name.contains('/') {
+                    #visitor_visit_child_match_any
+                }
+            };
+        }
+        if self.attr.allow_unknown_text {
+            visitor_visit_value.append_all(quote! {
+                #[allow(unreachable_code)]
                 Ok(())
+            });
+        } else {
+            visitor_visit_value.append_all(quote! {
+                #[allow(unreachable_code)]
+                Err(::async_xml::Error::UnexpectedText)
+            });
+        }
+
+        let mut visitor_build = TokenStream::new();
+        visitor_build.append_all(self.fields.iter().map(|f| f.visitor_build(false)));
+        let mut visitor_finish = TokenStream::new();
+        visitor_finish.append_all(self.fields.iter().map(|f| f.visitor_build(true)));
+
+        let visitor_doc = format!(
+            "A [`Visitor`](::async_xml::Visitor) for deserializing a [`{}`]",
+            name
+        );
+        let visitor = quote! {
+            #[doc = #visitor_doc]
+            #[doc(hidden)]
+            #vis struct #visitor_name #impl_generics #where_clause {
+                #visitor_fields
+                _phantom: core::marker::PhantomData<(B, fn() -> #output)>,
             }
-        })
-        .unwrap(),
-    );
-    let unknown_attr = if container.attr.allow_unknown_attributes {
-        TokenStream::new()
-    } else {
-        quote! { return Err(::async_xml::Error::UnexpectedAttribute(name.into())); }
-    };
-    visitor_impl.items.push(
-        syn::parse2(quote! {
-            fn visit_attribute(&mut self, name: &str, value: &str) -> ::core::result::Result<(), ::async_xml::Error> {
-                match name {
-                    #visitor_visit_attr_match
-                    _ => {
-                        #visitor_visit_attr_match_any
-                        #unknown_attr
+            impl #impl_generics Default for #visitor_name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self {
+                        #visitor_default
+                        _phantom: core::marker::PhantomData,
                     }
                 }
-                #[allow(unreachable_code)]
-                Ok(())
+            }
+        };
+        let mut visitor_impl: syn::ItemImpl = syn::parse2(quote! {
+            #[async_trait::async_trait(?Send)]
+            impl #impl_generics ::async_xml::Visitor<B> for #visitor_name #ty_generics #where_clause {
+                type Output = #output;
             }
         })
-        .unwrap(),
-    );
-    visitor_impl.items.push(
-        syn::parse2(quote! {
-            fn visit_text(&mut self, text: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+        .unwrap();
 
-                #[allow(unreachable_code)]
-                {
-                    #visitor_visit_value
+        let visitor_build_value = match self.struct_type {
+            StructType::Normal => quote! { #constructor { #visitor_build_fields } },
+            StructType::Newtype | StructType::Tuple => {
+                quote! { #constructor( #visitor_build_fields ) }
+            }
+            StructType::Unit => quote! { #constructor },
+        };
+        visitor_impl.items.push(
+            syn::parse2(quote! {
+                fn build(self) -> ::core::result::Result<#output, ::async_xml::Error> {
+                    #visitor_build
+
+                    Ok(#visitor_build_value)
+                }
+            })
+            .unwrap(),
+        );
+        let visitor_finish_value = if let Some(finish) = &self.attr.finish {
+            quote! { #finish(#visitor_build_value).await }
+        } else {
+            quote! { Ok(#visitor_build_value) }
+        };
+        visitor_impl.items.push(
+            syn::parse2(quote! {
+                async fn finish(self) -> ::core::result::Result<#output, ::async_xml::Error> {
+                    #visitor_finish
+
+                    #visitor_finish_value
+                }
+            })
+            .unwrap(),
+        );
+        visitor_impl.items.push(
+            syn::parse2(quote! {
+                fn start_name() -> Option<&'static str> {
+                    #tag_name
+                }
+            })
+            .unwrap(),
+        );
+        visitor_impl.items.push(
+            syn::parse2(quote! {
+                fn start_names() -> Vec<&'static str> {
+                    #tag_names
+                }
+            })
+            .unwrap(),
+        );
+        visitor_impl.items.push(
+            syn::parse2(quote! {
+                fn visit_tag(&mut self, name: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                    #visitor_visit_tag
+                    Ok(())
+                }
+            })
+            .unwrap(),
+        );
+        let unknown_attr = if self.attr.allow_unknown_attributes {
+            TokenStream::new()
+        } else {
+            quote! {
+                if !::async_xml::util::is_reserved_attribute(name) {
+                    return Err(::async_xml::Error::UnexpectedAttribute(name.into()));
                 }
             }
-        })
-        .unwrap(),
-    );
-    let unknown_child = if container.attr.allow_unknown_children {
-        quote! { reader.skip_element().await?; }
-    } else {
-        quote! { return Err(::async_xml::Error::UnexpectedChild(name.into())); }
-    };
-    visitor_impl.items.push(
-        syn::parse2(quote! {
-            async fn visit_child(
-                &mut self,
-                name: &str,
-                reader: &mut ::async_xml::reader::PeekingReader<B>,
-            ) -> ::core::result::Result<(), ::async_xml::Error> {
-                match name {
-                    #visitor_visit_child_match
-                    _ => {
-                        #visitor_visit_child_match_any
-                        #unknown_child
+        };
+        visitor_impl.items.push(
+            syn::parse2(quote! {
+                fn visit_attribute(&mut self, name: &str, value: &str) -> ::core::result::Result<(), ::async_xml::Error> {
+                    match name {
+                        #visitor_visit_attr_match
+                        #[allow(unreachable_code)]
+                        _ => {
+                            #visitor_visit_attr_match_any
+                            #unknown_attr
+                        }
                     }
+                    #[allow(unreachable_code)]
+                    Ok(())
                 }
-                #[allow(unreachable_code)]
-                Ok(())
-            }
-        })
-        .unwrap(),
-    );
+            })
+            .unwrap(),
+        );
+        visitor_impl.items.push(
+            syn::parse2(quote! {
+                fn visit_text(&mut self, text: &str) -> ::core::result::Result<(), ::async_xml::Error> {
 
-    let expanded = quote! {
-        #visitor
-        #visitor_impl
-        impl<B: ::tokio::io::AsyncBufRead + Unpin> ::async_xml::reader::FromXml<B> for #name {
-            type Visitor = #visitor_name<B>;
-        }
-    };
+                    #[allow(unreachable_code)]
+                    {
+                        #visitor_visit_value
+                    }
+                }
+            })
+            .unwrap(),
+        );
+        let unknown_child = if self.attr.allow_unknown_children {
+            quote! { reader.skip_element().await?; }
+        } else {
+            quote! { return Err(::async_xml::Error::UnexpectedChild(name.into())); }
+        };
+        visitor_impl.items.push(
+            syn::parse2(quote! {
+                async fn visit_child(
+                    &mut self,
+                    name: &str,
+                    reader: &mut ::async_xml::reader::PeekingReader<B>,
+                ) -> ::core::result::Result<(), ::async_xml::Error> {
+                    match name {
+                        #visitor_visit_child_match
+                        #[allow(unreachable_code)]
+                        _ => {
+                            #visitor_visit_child_match_any
+                            #unknown_child
+                        }
+                    }
+                    #[allow(unreachable_code)]
+                    Ok(())
+                }
+            })
+            .unwrap(),
+        );
 
-    Ok(expanded)
+        quote! {
+            #visitor
+            #visitor_impl
+        }
+    }
 }
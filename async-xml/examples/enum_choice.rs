@@ -0,0 +1,28 @@
+use async_xml::from_str;
+use async_xml_derive::FromXml;
+
+#[tokio::main]
+async fn main() {
+    let xml = r#"<payment><paypal email="a@b.c" /></payment>"#;
+    let de: Payment = from_str(xml).await.unwrap();
+    println!("deserialized: {:?}", de);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "payment")]
+pub enum Payment {
+    #[async_xml(rename = "card")]
+    Card(Card),
+    #[async_xml(rename = "paypal")]
+    Paypal {
+        #[async_xml(attribute)]
+        email: String,
+    },
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "card")]
+pub struct Card {
+    #[async_xml(attribute)]
+    number: String,
+}
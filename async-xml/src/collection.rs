@@ -0,0 +1,322 @@
+//! Traits for collecting repeated child elements and wrapping deserialized values
+//!
+//! Derived types use [`XmlCollection`] for fields collecting all child elements with the same name,
+//! [`XmlMap`] for fields collecting child elements by key and [`XmlWrapper`] for values stored behind a pointer,
+//! like recursive child elements.
+
+use crate::reader::{FromXml, PeekingReader, Visitor};
+use crate::Error;
+use std::{
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque},
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+    rc::Rc,
+    sync::Arc,
+};
+use tokio::io::AsyncBufRead;
+
+/// A collection repeated child elements can be collected into
+pub trait XmlCollection: Default {
+    /// Type of the collected items
+    type Item;
+
+    /// Add an item to the collection
+    fn push_item(&mut self, item: Self::Item);
+}
+
+impl<T> XmlCollection for Vec<T> {
+    type Item = T;
+
+    fn push_item(&mut self, item: Self::Item) {
+        self.push(item);
+    }
+}
+
+impl<T> XmlCollection for VecDeque<T> {
+    type Item = T;
+
+    fn push_item(&mut self, item: Self::Item) {
+        self.push_back(item);
+    }
+}
+
+impl<T> XmlCollection for LinkedList<T> {
+    type Item = T;
+
+    fn push_item(&mut self, item: Self::Item) {
+        self.push_back(item);
+    }
+}
+
+impl<T: Ord> XmlCollection for BTreeSet<T> {
+    type Item = T;
+
+    fn push_item(&mut self, item: Self::Item) {
+        self.insert(item);
+    }
+}
+
+impl<T: Ord> XmlCollection for BinaryHeap<T> {
+    type Item = T;
+
+    fn push_item(&mut self, item: Self::Item) {
+        self.push(item);
+    }
+}
+
+impl<T, S> XmlCollection for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Item = T;
+
+    fn push_item(&mut self, item: Self::Item) {
+        self.insert(item);
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> XmlCollection for smallvec::SmallVec<A> {
+    type Item = A::Item;
+
+    fn push_item(&mut self, item: Self::Item) {
+        self.push(item);
+    }
+}
+
+/// A visitor collecting the repeated child elements of a wrapper element, like `<items><item/><item/></items>`
+///
+/// As the wrapper element name has already been matched by the containing element, any starting tag name is
+/// accepted. An empty wrapper element results in an empty collection.
+pub struct WrappedVisitor<C, B>
+where
+    C: XmlCollection,
+    B: AsyncBufRead + Unpin,
+{
+    collection: C,
+    item_name: &'static str,
+    _phantom: PhantomData<B>,
+}
+
+impl<C, B> WrappedVisitor<C, B>
+where
+    C: XmlCollection,
+    B: AsyncBufRead + Unpin,
+{
+    /// Create a new [`WrappedVisitor`] adding child elements with the given name to the collection
+    pub fn new(collection: C, item_name: &'static str) -> Self {
+        Self {
+            collection,
+            item_name,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<C, B> Visitor<B> for WrappedVisitor<C, B>
+where
+    C: XmlCollection,
+    C::Item: FromXml<B>,
+    B: AsyncBufRead + Unpin,
+{
+    type Output = C;
+
+    async fn visit_child(
+        &mut self,
+        name: &str,
+        reader: &mut PeekingReader<B>,
+    ) -> Result<(), Error> {
+        if name != self.item_name {
+            return Err(Error::UnexpectedChild(name.into()));
+        }
+        let item = reader
+            .deserialize()
+            .await
+            .map_err(|e| Error::InnerDeserialiaztionError(name.into(), Box::new(e)))?;
+        self.collection.push_item(item);
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Error> {
+        Ok(self.collection)
+    }
+}
+
+/// A map child elements can be collected into by key
+pub trait XmlMap: Default {
+    /// Type of the keys
+    type Key;
+    /// Type of the values
+    type Value;
+
+    /// Insert an entry into the map, returning whether an entry with the same key has been replaced
+    fn insert_entry(&mut self, key: Self::Key, value: Self::Value) -> bool;
+}
+
+impl<K, V, S> XmlMap for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Key = K;
+    type Value = V;
+
+    fn insert_entry(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        self.insert(key, value).is_some()
+    }
+}
+
+impl<K: Ord, V> XmlMap for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn insert_entry(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        self.insert(key, value).is_some()
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> XmlMap for indexmap::IndexMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Key = K;
+    type Value = V;
+
+    fn insert_entry(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        self.insert(key, value).is_some()
+    }
+}
+
+/// Where the key of a map entry is read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapKey {
+    /// Read the key from the attribute with the given name, which isn't passed on to the value
+    Attribute(&'static str),
+    /// Use the tag name of the element as the key
+    Tag,
+}
+
+/// A visitor deserializing an element into a key and a value for an [`XmlMap`]
+///
+/// As the element name has already been matched by the containing element, any starting tag name is accepted.
+pub struct MapEntryVisitor<V, B>
+where
+    V: Visitor<B>,
+    B: AsyncBufRead + Unpin,
+{
+    inner: V,
+    key: MapKey,
+    found_key: Option<String>,
+    _phantom: PhantomData<B>,
+}
+
+impl<V, B> MapEntryVisitor<V, B>
+where
+    V: Visitor<B>,
+    B: AsyncBufRead + Unpin,
+{
+    /// Create a new [`MapEntryVisitor`] reading the key from the given location
+    pub fn new(inner: V, key: MapKey) -> Self {
+        Self {
+            inner,
+            key,
+            found_key: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn key(&mut self) -> Result<String, Error> {
+        match (self.found_key.take(), self.key) {
+            (Some(key), _) => Ok(key),
+            (None, MapKey::Attribute(name)) => Err(Error::MissingAttribute(name.into())),
+            (None, MapKey::Tag) => Err(Error::MissingStart),
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<V, B> Visitor<B> for MapEntryVisitor<V, B>
+where
+    V: Visitor<B>,
+    B: AsyncBufRead + Unpin,
+{
+    type Output = (String, V::Output);
+
+    fn visit_tag(&mut self, name: &str) -> Result<(), Error> {
+        if self.key == MapKey::Tag {
+            self.found_key = Some(name.into());
+        }
+        self.inner.visit_tag(name)
+    }
+
+    fn visit_attribute(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match self.key {
+            MapKey::Attribute(key) if key == name => {
+                if self.found_key.replace(value.into()).is_some() {
+                    return Err(Error::UnexpectedAttribute(name.into()));
+                }
+                Ok(())
+            }
+            _ => self.inner.visit_attribute(name, value),
+        }
+    }
+
+    async fn visit_child(
+        &mut self,
+        name: &str,
+        reader: &mut PeekingReader<B>,
+    ) -> Result<(), Error> {
+        self.inner.visit_child(name, reader).await
+    }
+
+    fn visit_text(&mut self, text: &str) -> Result<(), Error> {
+        self.inner.visit_text(text)
+    }
+
+    fn build(mut self) -> Result<Self::Output, Error> {
+        let key = self.key()?;
+        Ok((key, self.inner.build()?))
+    }
+
+    async fn finish(mut self) -> Result<Self::Output, Error> {
+        let key = self.key()?;
+        Ok((key, self.inner.finish().await?))
+    }
+}
+
+/// A pointer type wrapping a deserialized value
+pub trait XmlWrapper {
+    /// Type of the deserialized value
+    type Inner;
+
+    /// Wrap a deserialized value
+    fn wrap(inner: Self::Inner) -> Self;
+}
+
+macro_rules! impl_wrapper {
+    ($($wrapper:ident),*) => {
+        $(
+            impl<T> XmlWrapper for $wrapper<T> {
+                type Inner = T;
+
+                fn wrap(inner: Self::Inner) -> Self {
+                    $wrapper::new(inner)
+                }
+            }
+
+            impl XmlWrapper for $wrapper<str> {
+                type Inner = String;
+
+                fn wrap(inner: Self::Inner) -> Self {
+                    inner.into()
+                }
+            }
+        )*
+    };
+}
+
+impl_wrapper!(Box, Rc, Arc);
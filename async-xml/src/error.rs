@@ -32,21 +32,48 @@ pub enum Error {
     /// Encountered multiple child elements with the given name when only one was expected
     #[error("Found multiple child elements <{0}>, but only expected one")]
     DoubleChild(String),
+    /// Encountered multiple attributes setting the same field, e.g. through aliases
+    #[error("Found attribute {0} for a field that was already set")]
+    DoubleAttribute(String),
     /// Encountered multiple text events when only one was expected
     #[error("Element contains multiple text events")]
     DoubleText,
+    /// Encountered the same key multiple times for a map that doesn't allow duplicates
+    #[error("Found duplicate key {0}")]
+    DuplicateKey(String),
     /// Encountered an unexpected attribute
     #[error("Found unexpected attribute {0}")]
     UnexpectedAttribute(String),
     /// Encountered an unexpected child element
     #[error("Found unexpected child element <{0}>")]
     UnexpectedChild(String),
+    /// Encountered a number of child elements outside of the expected range
+    #[error(
+        "Found {1} child elements <{0}>, but expected {}",
+        format_occurrences(*.2, *.3)
+    )]
+    UnexpectedOccurrences(String, usize, usize, Option<usize>),
+    /// Encountered a child element after a child element that should follow it
+    #[error("Found child element <{0}> out of order")]
+    UnexpectedOrder(String),
     /// Encountered an unexpected text event
     #[error("Found unexpected text")]
     UnexpectedText,
+    /// Encountered a value that doesn't match any enum variant
+    #[error("Unknown variant {0}")]
+    UnknownVariant(String),
+    /// None of the variants of an untagged enum could be deserialized
+    #[error("No variant matched: {}", format_variant_errors(.0))]
+    NoMatchingVariant(Vec<(String, Error)>),
+    /// Encountered an element no type has been registered for
+    #[error("No type registered for element <{0}>")]
+    UnregisteredElement(String),
     /// Bubbling deserialization error
     #[error("Error deserializing element <{0}>: {1}")]
     InnerDeserialiaztionError(String, Box<Error>),
+    /// The receiving end of pushed input has been dropped
+    #[error("Parser has been closed")]
+    Closed,
     /// General deserialization error
     #[error("Deserialization error: {0}")]
     Deserialization(String),
@@ -60,3 +87,20 @@ where
         Self::Xml(e.into())
     }
 }
+
+fn format_occurrences(min: usize, max: Option<usize>) -> String {
+    match max {
+        Some(max) if min == max => format!("exactly {}", min),
+        Some(max) if min == 0 => format!("at most {}", max),
+        Some(max) => format!("between {} and {}", min, max),
+        None => format!("at least {}", min),
+    }
+}
+
+fn format_variant_errors(errors: &[(String, Error)]) -> String {
+    errors
+        .iter()
+        .map(|(variant, e)| format!("{}: {}", variant, e))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -0,0 +1,192 @@
+//! Event-based processing of XML input without deserializing into types
+
+use crate::{reader::FromXml, util::XmlAttribute, Error, PeekingReader};
+use quick_xml::events::Event;
+use tokio::io::AsyncBufRead;
+
+/// A trait for receiving XML events along with the path of the element they belong to
+///
+/// All paths contain the tag names of all ancestors from the root element down to and including the current element.
+///
+/// As with [`Visitor`](crate::Visitor), this trait must be `?Send`.
+#[async_trait::async_trait(?Send)]
+pub trait Handler<B: AsyncBufRead + Unpin> {
+    /// Handle the start of an element
+    ///
+    /// The element's start tag hasn't been consumed yet when this is called, so the entire element may be consumed
+    /// using [`element.deserialize()`](StartElement::deserialize) or [`element.skip()`](StartElement::skip). No
+    /// further events will be emitted for consumed elements.
+    #[allow(unused_variables)]
+    async fn start_element(&mut self, element: &mut StartElement<'_, B>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Handle an attribute of the current element
+    #[allow(unused_variables)]
+    fn attribute(&mut self, path: &[String], name: &str, value: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Handle any plain text contained in the current element
+    ///
+    /// May be called multiple times per element.
+    #[allow(unused_variables)]
+    fn text(&mut self, path: &[String], text: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Handle the end of the current element
+    #[allow(unused_variables)]
+    fn end_element(&mut self, path: &[String]) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// The start of an element passed to [`Handler::start_element()`]
+pub struct StartElement<'a, B: AsyncBufRead + Unpin> {
+    reader: &'a mut PeekingReader<B>,
+    path: &'a [String],
+    attributes: Vec<XmlAttribute>,
+    consumed: bool,
+}
+
+impl<'a, B: AsyncBufRead + Unpin> StartElement<'a, B> {
+    /// Tag name of the element
+    pub fn name(&self) -> &str {
+        self.path.last().unwrap()
+    }
+
+    /// Path of the element, including its own tag name
+    pub fn path(&self) -> &[String] {
+        self.path
+    }
+
+    /// Attributes of the element
+    pub fn attributes(&self) -> &[XmlAttribute] {
+        &self.attributes
+    }
+
+    /// Consume the entire element and deserialize it into a `T`
+    pub async fn deserialize<T>(&mut self) -> Result<T, Error>
+    where
+        T: FromXml<B>,
+    {
+        self.consume()?;
+        self.reader.deserialize().await
+    }
+
+    /// Consume and discard the entire element
+    pub async fn skip(&mut self) -> Result<(), Error> {
+        self.consume()?;
+        self.reader.skip_element().await
+    }
+
+    fn consume(&mut self) -> Result<(), Error> {
+        if self.consumed {
+            return Err(Error::Deserialization(format!(
+                "element <{}> already consumed",
+                self.name()
+            )));
+        }
+        self.consumed = true;
+        Ok(())
+    }
+}
+
+impl<B: AsyncBufRead + Unpin> PeekingReader<B> {
+    /// Read a single element from the XML input, passing all of its events to the given handler
+    pub async fn handle<H>(&mut self, handler: &mut H) -> Result<(), Error>
+    where
+        H: Handler<B>,
+    {
+        let dec = self.decoder();
+        let mut path: Vec<String> = Vec::new();
+
+        loop {
+            match self.peek_event().await? {
+                Event::Start(start) => {
+                    let name = start.local_name();
+                    let name = dec.decode(name.as_ref())?.to_string();
+                    let mut attributes = Vec::new();
+                    for attr in start.attributes() {
+                        let attr = attr?;
+                        let attr_name = dec.decode(attr.key.as_ref())?;
+                        let attr_value = dec.decode(attr.value.as_ref())?;
+                        let attr_value = quick_xml::escape::unescape(&attr_value)?;
+                        attributes.push(XmlAttribute {
+                            name: attr_name.into(),
+                            value: attr_value.into(),
+                        });
+                    }
+                    tracing::trace!("handling start of element <{}>", name);
+                    path.push(name);
+
+                    let mut element = StartElement {
+                        reader: self,
+                        path: &path,
+                        attributes,
+                        consumed: false,
+                    };
+                    handler.start_element(&mut element).await?;
+                    if element.consumed {
+                        tracing::trace!("element consumed by handler");
+                        path.pop();
+                        if path.is_empty() {
+                            return Ok(());
+                        }
+                        continue;
+                    }
+                    let attributes = element.attributes;
+
+                    // remove peeked start event
+                    self.read_event().await?;
+                    for attr in attributes {
+                        handler.attribute(&path, &attr.name, &attr.value)?;
+                    }
+                }
+                Event::End(end) if !path.is_empty() => {
+                    let name = end.local_name();
+                    let name = dec.decode(name.as_ref())?.to_string();
+                    // remove peeked end event
+                    self.read_event().await?;
+                    let start_tag = path.last().unwrap();
+                    if &name != start_tag {
+                        return Err(Error::WrongEnd(start_tag.clone(), name));
+                    }
+                    tracing::trace!("handling end of element <{}>", name);
+                    handler.end_element(&path)?;
+                    path.pop();
+                    if path.is_empty() {
+                        return Ok(());
+                    }
+                }
+                Event::Text(text) if !path.is_empty() => {
+                    let text = dec.decode(text)?;
+                    let text = quick_xml::escape::unescape(&text)?;
+                    handler.text(&path, &text)?;
+                    // remove peeked event
+                    self.read_event().await?;
+                }
+                // the prolog before the root element is skipped
+                Event::Decl(_) | Event::Comment(_) | Event::PI(_) | Event::DocType(_)
+                    if path.is_empty() =>
+                {
+                    self.read_event().await?;
+                }
+                _ if path.is_empty() => {
+                    return Err(Error::MissingStart);
+                }
+                Event::Eof => {
+                    return Err(quick_xml::Error::UnexpectedEof(format!(
+                        "</{}>",
+                        path.last().unwrap()
+                    ))
+                    .into());
+                }
+                _ => {
+                    self.read_event().await?;
+                }
+            }
+        }
+    }
+}
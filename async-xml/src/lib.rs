@@ -2,8 +2,12 @@
 
 #![warn(missing_docs)]
 
+pub mod collection;
 pub mod error;
+pub mod handler;
+pub mod push;
 pub mod reader;
+pub mod registry;
 pub mod util;
 
 #[cfg(test)]
@@ -11,6 +15,7 @@ mod test;
 
 pub use self::error::{Error, Result};
 pub use self::reader::{PeekingReader, Visitor};
+pub use self::registry::Registry;
 pub use self::util::XmlVec;
 
 #[cfg(feature = "derive")]
@@ -0,0 +1,190 @@
+//! Incremental parsing of XML input pushed in arbitrary chunks
+
+use crate::{reader::FromXml, Error, PeekingReader};
+use bytes::{Buf, Bytes};
+use quick_xml::events::Event;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncBufRead, AsyncRead, ReadBuf},
+    sync::mpsc,
+};
+
+/// Create a new [`PushParser`] along with the [`ChunkSender`] feeding it
+pub fn push_parser() -> (ChunkSender, PushParser) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let reader = ChunkReader {
+        receiver,
+        chunk: Bytes::new(),
+    };
+    (ChunkSender { sender }, PushParser::new(reader))
+}
+
+/// The sending half of a [`PushParser`]
+///
+/// Chunks may be split at arbitrary positions, even inside of tags or multi-byte characters. Dropping all senders
+/// marks the end of the input.
+#[derive(Clone)]
+pub struct ChunkSender {
+    sender: mpsc::UnboundedSender<Bytes>,
+}
+
+impl ChunkSender {
+    /// Push a chunk of XML input to the parser
+    pub fn push(&self, chunk: impl Into<Bytes>) -> Result<(), Error> {
+        self.sender.send(chunk.into()).map_err(|_| Error::Closed)
+    }
+
+    /// Mark the end of the input
+    ///
+    /// This is equivalent to dropping the sender, but only if all of its clones have been dropped as well.
+    pub fn finish(self) {}
+}
+
+/// A buffered reader returning chunks received from a [`ChunkSender`]
+pub struct ChunkReader {
+    receiver: mpsc::UnboundedReceiver<Bytes>,
+    chunk: Bytes,
+}
+
+impl AsyncRead for ChunkReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let available = match self.as_mut().poll_fill_buf(cx) {
+            Poll::Ready(Ok(available)) => available,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+        let len = available.len().min(buf.remaining());
+        buf.put_slice(&available[..len]);
+        self.consume(len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncBufRead for ChunkReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        let this = self.get_mut();
+        while this.chunk.is_empty() {
+            match this.receiver.poll_recv(cx) {
+                Poll::Ready(Some(chunk)) => this.chunk = chunk,
+                // all senders dropped, signal EOF
+                Poll::Ready(None) => break,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(&this.chunk))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().chunk.advance(amt);
+    }
+}
+
+/// A parser driven by chunks of XML input pushed through a [`ChunkSender`]
+///
+/// Items are deserialized as soon as all of their input has been pushed, either as a sequence of top-level elements
+/// using [`next()`](Self::next) or as the children of a single root element using
+/// [`next_child()`](Self::next_child).
+pub struct PushParser {
+    reader: PeekingReader<ChunkReader>,
+    root: Option<String>,
+    root_done: bool,
+}
+
+impl PushParser {
+    fn new(reader: ChunkReader) -> Self {
+        Self {
+            reader: PeekingReader::from_buf(reader),
+            root: None,
+            root_done: false,
+        }
+    }
+
+    /// Get the underlying reader
+    pub fn reader(&mut self) -> &mut PeekingReader<ChunkReader> {
+        &mut self.reader
+    }
+
+    /// Deserialize the next top-level element
+    ///
+    /// Returns [`None`] once the input has ended.
+    pub async fn next<T>(&mut self) -> Result<Option<T>, Error>
+    where
+        T: FromXml<ChunkReader>,
+    {
+        loop {
+            match self.reader.peek_event().await? {
+                Event::Start(_) => return self.reader.deserialize().await.map(Some),
+                Event::Eof => return Ok(None),
+                _ => {
+                    self.reader.read_event().await?;
+                }
+            }
+        }
+    }
+
+    /// Deserialize the next child element of the root element
+    ///
+    /// The root element's start tag is consumed on the first call. Returns [`None`] once the root element has ended.
+    pub async fn next_child<T>(&mut self) -> Result<Option<T>, Error>
+    where
+        T: FromXml<ChunkReader>,
+    {
+        if self.root_done {
+            return Ok(None);
+        }
+        let dec = self.reader.decoder();
+        let root = match &self.root {
+            Some(root) => root.clone(),
+            None => loop {
+                match self.reader.peek_event().await? {
+                    Event::Start(start) => {
+                        let name = start.local_name();
+                        let name = dec.decode(name.as_ref())?.to_string();
+                        tracing::debug!("entering root element <{}>", name);
+                        // the root's namespace declarations stay in scope for all of its children
+                        let attributes = crate::reader::decode_attributes(dec, start)?;
+                        self.reader.declare_namespaces(&attributes);
+                        // remove peeked start event
+                        self.reader.read_event().await?;
+                        self.root = Some(name.clone());
+                        break name;
+                    }
+                    Event::Eof => return Err(Error::MissingStart),
+                    _ => {
+                        self.reader.read_event().await?;
+                    }
+                }
+            },
+        };
+
+        loop {
+            match self.reader.peek_event().await? {
+                Event::Start(_) => return self.reader.deserialize().await.map(Some),
+                Event::End(end) => {
+                    let name = end.local_name();
+                    let name = dec.decode(name.as_ref())?.to_string();
+                    // remove peeked end event
+                    self.reader.read_event().await?;
+                    if name != root {
+                        return Err(Error::WrongEnd(root, name));
+                    }
+                    self.root_done = true;
+                    return Ok(None);
+                }
+                Event::Eof => {
+                    return Err(quick_xml::Error::UnexpectedEof(format!("</{}>", root)).into());
+                }
+                _ => {
+                    self.reader.read_event().await?;
+                }
+            }
+        }
+    }
+}
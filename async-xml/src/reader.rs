@@ -1,8 +1,9 @@
 //! Deserialization implementations
 
 use crate::Error;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::Decoder;
+use std::marker::PhantomData;
 use tokio::io::AsyncBufRead;
 use tracing::Instrument;
 
@@ -17,6 +18,8 @@ pub type XmlReader<R> = quick_xml::Reader<R>;
 pub struct PeekingReader<B: AsyncBufRead> {
     reader: XmlReader<B>,
     peeked_event: Option<Event<'static>>,
+    /// namespace declarations in scope of the element being deserialized, innermost last
+    namespaces: Vec<(String, String)>,
 }
 
 impl<B: AsyncBufRead + Unpin> PeekingReader<B> {
@@ -27,9 +30,34 @@ impl<B: AsyncBufRead + Unpin> PeekingReader<B> {
         Self {
             reader,
             peeked_event: None,
+            namespaces: Vec::new(),
         }
     }
 
+    /// Namespace declarations in scope of the element being deserialized
+    ///
+    /// These are `xmlns` and `xmlns:*` attributes of the element and all of its ancestors, innermost last.
+    pub fn namespaces(&self) -> &[(String, String)] {
+        &self.namespaces
+    }
+
+    /// Resolve the prefix of a qualified name using the namespace declarations in scope
+    ///
+    /// See [`resolve_prefix()`](crate::util::resolve_prefix).
+    pub fn resolve_prefix<'a>(&'a self, name: &'a str) -> (Option<&'a str>, &'a str) {
+        crate::util::resolve_prefix(&self.namespaces, name)
+    }
+
+    /// Add namespace declarations among the given attributes of an element being entered to the scope
+    pub(crate) fn declare_namespaces(&mut self, attributes: &[(String, String)]) {
+        self.namespaces.extend(
+            attributes
+                .iter()
+                .filter(|(name, _)| name == "xmlns" || name.starts_with("xmlns:"))
+                .cloned(),
+        );
+    }
+
     /// Consume this [`PeekingReader`] and returns the underlying buffered reader
     pub fn into_inner(self) -> B {
         self.reader.into_inner()
@@ -126,7 +154,37 @@ impl<B: AsyncBufRead + Unpin> PeekingReader<B> {
     where
         T: FromXml<B>,
     {
-        let mut visitor = T::Visitor::default();
+        self.deserialize_with_visitor(T::Visitor::default()).await
+    }
+
+    /// Read a single element from the XML input and deserialize it using the given seed
+    ///
+    /// This is the stateful counterpart to [`deserialize()`](Self::deserialize), see [`DeserializeSeed`].
+    pub async fn deserialize_seed<S>(&mut self, seed: S) -> Result<S::Value, Error>
+    where
+        S: DeserializeSeed<B>,
+    {
+        self.deserialize_with_visitor(seed.into_visitor()).await
+    }
+
+    /// Read a single element from the XML input and deserialize it using an already constructed visitor
+    ///
+    /// Unlike [`deserialize()`](Self::deserialize), this allows using visitors configured at runtime.
+    pub async fn deserialize_with_visitor<V>(&mut self, visitor: V) -> Result<V::Output, Error>
+    where
+        V: Visitor<B>,
+    {
+        // namespaces declared on the element go out of scope with it, even if deserialization failed
+        let scope = self.namespaces.len();
+        let result = self.deserialize_element(visitor).await;
+        self.namespaces.truncate(scope);
+        result
+    }
+
+    async fn deserialize_element<V>(&mut self, mut visitor: V) -> Result<V::Output, Error>
+    where
+        V: Visitor<B>,
+    {
         let dec = self.reader.decoder();
 
         let start_tag;
@@ -137,22 +195,24 @@ impl<B: AsyncBufRead + Unpin> PeekingReader<B> {
                 let name = start.local_name();
                 let name = dec.decode(name.as_ref())?;
                 tracing::debug!("deserializing XML element <{}>", name);
-                if let Some(expected_name) = T::Visitor::start_name() {
-                    if name != expected_name {
-                        return Err(Error::WrongStart(expected_name.into(), name.into()));
-                    }
-                }
+                check_start_name::<V, B>(&name)?;
                 // store name to match expected end element
                 start_tag = name.to_string();
                 element_span = tracing::debug_span!("deserialize", element = start_tag);
                 let span_guard = element_span.enter();
                 visitor.visit_tag(&name)?;
-                // read attributes
-                for attr in start.attributes() {
-                    let attr = attr?;
-                    let attr_name = dec.decode(attr.key.as_ref())?;
-                    let attr_value = dec.decode(attr.value.as_ref())?;
-                    let attr_value = quick_xml::escape::unescape(&attr_value)?;
+                // read all attributes first, as prefixes may be declared by any of them
+                let attributes = decode_attributes(dec, start)?;
+                self.declare_namespaces(&attributes);
+                for (attr_name, attr_value) in &attributes {
+                    let attr_name =
+                        crate::util::canonical_attribute_name(&self.namespaces, attr_name);
+                    // the type is a qualified name, which is resolved while its declaration is in scope
+                    let attr_value = if attr_name == "xsi:type" {
+                        crate::util::expand_qualified_name(&self.namespaces, attr_value)
+                    } else {
+                        attr_value.into()
+                    };
                     tracing::trace!("visiting attribute: {:?}", attr_name);
                     visitor.visit_attribute(&attr_name, &attr_value)?;
                 }
@@ -181,10 +241,10 @@ impl<B: AsyncBufRead + Unpin> PeekingReader<B> {
                             return Err(Error::WrongEnd(start_tag, name));
                         }
                         tracing::trace!("finishing deserialization of XML element <{}>", name);
-                        return visitor.build();
+                        return visitor.finish().await;
                     }
                     Event::Text(text) => {
-                        let text = dec.decode(&text)?;
+                        let text = dec.decode(text)?;
                         let text = quick_xml::escape::unescape(&text)?;
                         tracing::trace!("visiting element text");
                         visitor.visit_text(&text)?;
@@ -209,6 +269,36 @@ impl<B: AsyncBufRead + Unpin> PeekingReader<B> {
     }
 }
 
+/// Decode the names and unescaped values of all attributes of a start element
+pub(crate) fn decode_attributes(
+    dec: Decoder,
+    start: &BytesStart,
+) -> Result<Vec<(String, String)>, Error> {
+    let mut attributes = Vec::new();
+    for attr in start.attributes() {
+        let attr = attr?;
+        let attr_name = dec.decode(attr.key.as_ref())?;
+        let attr_value = dec.decode(attr.value.as_ref())?;
+        let attr_value = quick_xml::escape::unescape(&attr_value)?;
+        attributes.push((attr_name.into_owned(), attr_value.into_owned()));
+    }
+    Ok(attributes)
+}
+
+/// Check whether a visitor accepts the given starting tag name
+pub(crate) fn check_start_name<V, B>(name: &str) -> Result<(), Error>
+where
+    V: Visitor<B>,
+    B: AsyncBufRead + Unpin,
+{
+    let expected_names = V::start_names();
+    if expected_names.is_empty() || expected_names.contains(&name) {
+        Ok(())
+    } else {
+        Err(Error::WrongStart(expected_names.join("|"), name.into()))
+    }
+}
+
 impl<'r> PeekingReader<&'r [u8]> {
     /// Create a new [`PeekingReader`] reading XML event from a [`str`].
     #[allow(clippy::should_implement_trait)]
@@ -223,6 +313,37 @@ pub trait FromXml<B: AsyncBufRead + Unpin> {
     type Visitor: Visitor<B, Output = Self> + Default;
 }
 
+/// A stateful counterpart to [`FromXml`]
+///
+/// Where [`FromXml`] always starts out with a [`Default`] visitor, a seed carries its own state into the visitor
+/// it creates. This allows deserialization to be parameterized at runtime.
+///
+/// Derived visitors are always created through [`Default`], so a `#[async_xml(seed = "path")]` field calls the
+/// given function without arguments for every child element. The seed can't depend on the parent's own seed or on
+/// previously visited fields; deserialize such children by implementing [`Visitor`] for the parent by hand.
+pub trait DeserializeSeed<B: AsyncBufRead + Unpin> {
+    /// The type produced by this seed
+    type Value;
+    /// The visitor to use to deserialize the value
+    type Visitor: Visitor<B, Output = Self::Value>;
+
+    /// Create the visitor to deserialize with
+    fn into_visitor(self) -> Self::Visitor;
+}
+
+impl<B, T> DeserializeSeed<B> for PhantomData<T>
+where
+    B: AsyncBufRead + Unpin,
+    T: FromXml<B>,
+{
+    type Value = T;
+    type Visitor = T::Visitor;
+
+    fn into_visitor(self) -> Self::Visitor {
+        T::Visitor::default()
+    }
+}
+
 /// A trait for building up instances of types during deserialization
 ///
 /// As [`XmlReader::read_event_into_async()`](quick_xml::Reader::read_event_into_async) does not return a `Send`
@@ -237,6 +358,13 @@ pub trait Visitor<B: AsyncBufRead + Unpin> {
         None
     }
 
+    /// Should return all accepted starting tag names, starting with the [`start_name()`](Self::start_name)
+    ///
+    /// Any tag name is accepted if this is empty.
+    fn start_names() -> Vec<&'static str> {
+        Self::start_name().into_iter().collect()
+    }
+
     /// Visit the starting tag with the given name
     ///
     /// This is called exactly once during deserialization and will be called before any other `visit_*` methods.
@@ -246,8 +374,16 @@ pub trait Visitor<B: AsyncBufRead + Unpin> {
     }
 
     /// Visit an attribute with the given name and value
+    ///
+    /// Attributes in the XML schema instance namespace are always visited with the `xsi` prefix, see
+    /// [`canonical_attribute_name()`](crate::util::canonical_attribute_name), and the value of `xsi:type` is
+    /// [expanded](crate::util::expand_qualified_name) using the namespaces in scope. By default, only
+    /// [reserved attributes](crate::util::is_reserved_attribute) are accepted.
     #[allow(unused_variables)]
     fn visit_attribute(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        if crate::util::is_reserved_attribute(name) {
+            return Ok(());
+        }
         Err(Error::UnexpectedAttribute(name.into()))
     }
 
@@ -275,4 +411,16 @@ pub trait Visitor<B: AsyncBufRead + Unpin> {
 
     /// Validate and build the output type
     fn build(self) -> Result<Self::Output, Error>;
+
+    /// Build the output type, allowing for asynchronous finalization
+    ///
+    /// This is called instead of [`build()`](Self::build) by [`PeekingReader`] and defaults to just calling
+    /// [`build()`](Self::build). Visitors wrapping other visitors should forward this to the inner visitor's
+    /// [`finish()`](Self::finish).
+    async fn finish(self) -> Result<Self::Output, Error>
+    where
+        Self: Sized,
+    {
+        self.build()
+    }
 }
@@ -51,6 +51,14 @@ where
         T::Visitor::start_name()
     }
 
+    fn start_names() -> Vec<&'static str> {
+        T::Visitor::start_names()
+    }
+
+    fn visit_tag(&mut self, name: &str) -> Result<(), Error> {
+        self.inner_visitor.visit_tag(name)
+    }
+
     fn visit_attribute(&mut self, name: &str, value: &str) -> Result<(), Error> {
         self.empty = false;
         self.inner_visitor.visit_attribute(name, value)
@@ -77,6 +85,14 @@ where
             Err(e) => Err(e),
         }
     }
+
+    async fn finish(self) -> Result<Self::Output, Error> {
+        match self.inner_visitor.finish().await {
+            Ok(t) => Ok(Some(t)),
+            Err(_) if self.empty => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 /// Marker trait for auto-implementing the [`FromXml`] trait based on the [`FromStr`]-implementation of a type.
@@ -208,6 +224,11 @@ where
         let from = self.inner.build()?;
         Ok(from.into())
     }
+
+    async fn finish(self) -> Result<Self::Output, Error> {
+        let from = self.inner.finish().await?;
+        Ok(from.into())
+    }
 }
 
 /// Generic visitor for forwarding all calls to an inner visitor and converting into the target
@@ -275,6 +296,12 @@ where
         from.try_into()
             .map_err(|e| Error::Deserialization(format!("error converting: {}", e)))
     }
+
+    async fn finish(self) -> Result<Self::Output, Error> {
+        let from = self.inner.finish().await?;
+        from.try_into()
+            .map_err(|e| Error::Deserialization(format!("error converting: {}", e)))
+    }
 }
 
 impl XmlFromStr for String {}
@@ -0,0 +1,142 @@
+//! Runtime dispatch of root elements to types by their tag name
+
+use crate::{reader::FromXml, Error, PeekingReader, Visitor};
+use quick_xml::events::Event;
+use std::{any::Any, collections::HashMap, future::Future, pin::Pin, rc::Rc};
+use tokio::io::AsyncBufRead;
+
+type DeserializeFuture<'r, O> = Pin<Box<dyn Future<Output = Result<O, Error>> + 'r>>;
+type DeserializeFn<B, O> = Rc<dyn for<'r> Fn(&'r mut PeekingReader<B>) -> DeserializeFuture<'r, O>>;
+
+/// A registry of types deserializable from XML, keyed by the name of their root element
+///
+/// Types are registered by all of their [`Visitor::start_names()`]. [`deserialize()`](Self::deserialize) peeks the next
+/// element's tag name and deserializes it into the matching type. The output type `O` is shared by all registered
+/// types and defaults to a boxed [`Any`], which can be downcast back to the concrete type.
+pub struct Registry<B, O = Box<dyn Any>>
+where
+    B: AsyncBufRead + Unpin,
+{
+    entries: HashMap<&'static str, DeserializeFn<B, O>>,
+}
+
+impl<B, O> Default for Registry<B, O>
+where
+    B: AsyncBufRead + Unpin,
+{
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+}
+
+impl<B, O> Registry<B, O>
+where
+    B: AsyncBufRead + Unpin,
+{
+    /// Create a new, empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a type, converting it to the output type using the given function
+    ///
+    /// Registering another type with the same tag name replaces the previous registration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type's visitor doesn't define a [`start_name()`](Visitor::start_name).
+    pub fn register_with<T, F>(&mut self, f: F) -> &mut Self
+    where
+        T: FromXml<B> + 'static,
+        F: Fn(T) -> O + 'static,
+    {
+        let f = Rc::new(f);
+        self.insert::<T>(Rc::new(move |reader| {
+            let f = Rc::clone(&f);
+            Box::pin(async move { Ok(f(reader.deserialize::<T>().await?)) })
+        }))
+    }
+
+    /// Register a type along with an async handler that is invoked with every deserialized instance
+    ///
+    /// The handler's result is returned from [`deserialize()`](Self::deserialize). Registering another type with
+    /// the same tag name replaces the previous registration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type's visitor doesn't define a [`start_name()`](Visitor::start_name).
+    pub fn register_handler<T, F, Fut>(&mut self, handler: F) -> &mut Self
+    where
+        T: FromXml<B> + 'static,
+        F: Fn(T) -> Fut + 'static,
+        Fut: Future<Output = Result<O, Error>> + 'static,
+    {
+        let handler = Rc::new(handler);
+        self.insert::<T>(Rc::new(move |reader| {
+            let handler = Rc::clone(&handler);
+            Box::pin(async move { handler(reader.deserialize::<T>().await?).await })
+        }))
+    }
+
+    fn insert<T>(&mut self, f: DeserializeFn<B, O>) -> &mut Self
+    where
+        T: FromXml<B>,
+    {
+        let names = T::Visitor::start_names();
+        if names.is_empty() {
+            panic!(
+                "cannot register {} without a start name",
+                std::any::type_name::<T>()
+            );
+        }
+        for name in names {
+            self.entries.insert(name, Rc::clone(&f));
+        }
+        self
+    }
+
+    /// Check whether a type is registered for the given tag name
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Peek the next element's tag name and deserialize it using the type registered for that name
+    pub async fn deserialize(&self, reader: &mut PeekingReader<B>) -> Result<O, Error> {
+        let dec = reader.decoder();
+        let name = match reader.peek_event().await? {
+            Event::Start(start) => {
+                let name = start.local_name();
+                dec.decode(name.as_ref())?.to_string()
+            }
+            _ => return Err(Error::MissingStart),
+        };
+        match self.entries.get(name.as_str()) {
+            Some(f) => {
+                tracing::debug!("dispatching element <{}>", name);
+                f(reader).await
+            }
+            None => Err(Error::UnregisteredElement(name)),
+        }
+    }
+}
+
+impl<B> Registry<B>
+where
+    B: AsyncBufRead + Unpin,
+{
+    /// Register a type, returning deserialized instances as a boxed [`Any`]
+    ///
+    /// Registering another type with the same tag name replaces the previous registration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type's visitor doesn't define a [`start_name()`](Visitor::start_name).
+    pub fn register<T>(&mut self) -> &mut Self
+    where
+        T: FromXml<B> + 'static,
+    {
+        self.register_with(|t: T| Box::new(t) as Box<dyn Any>)
+    }
+}
@@ -4,7 +4,10 @@ use crate::{
     reader::{FromXml, XmlFromStr},
     Error, Visitor,
 };
+use quick_xml::events::Event;
 use std::{
+    borrow::Cow,
+    io::Cursor,
     ops::{Deref, DerefMut},
     str::FromStr,
 };
@@ -161,6 +164,20 @@ where
     }
 }
 
+impl<V, B> DiscardErrorVisitor<V, B>
+where
+    B: AsyncBufRead + Unpin,
+    V: Visitor<B>,
+{
+    /// Wrap an already constructed visitor
+    pub fn new(inner_visitor: V) -> Self {
+        Self {
+            inner_visitor,
+            _phantom: core::marker::PhantomData,
+        }
+    }
+}
+
 #[async_trait::async_trait(?Send)]
 impl<V, B> Visitor<B> for DiscardErrorVisitor<V, B>
 where
@@ -173,6 +190,14 @@ where
         V::start_name()
     }
 
+    fn start_names() -> Vec<&'static str> {
+        V::start_names()
+    }
+
+    fn visit_tag(&mut self, name: &str) -> Result<(), Error> {
+        self.inner_visitor.visit_tag(name)
+    }
+
     fn visit_attribute(&mut self, name: &str, value: &str) -> Result<(), Error> {
         self.inner_visitor.visit_attribute(name, value)
     }
@@ -198,4 +223,417 @@ where
             }
         }
     }
+
+    async fn finish(self) -> Result<Self::Output, Error> {
+        match self.inner_visitor.finish().await {
+            Ok(t) => Ok(Some(t)),
+            Err(e) => {
+                tracing::trace!("discarding build error: {:?}", e);
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Namespace of XML schema instance attributes like `xsi:type`
+pub const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// Check whether an attribute is reserved for namespace declarations or XML schema instance information
+///
+/// Reserved attributes are `xmlns`, `xmlns:*` and `xsi:*`. Derived visitors ignore them instead of returning
+/// [`Error::UnexpectedAttribute`]. Attributes using another prefix bound to the [`XSI_NAMESPACE`] are passed to
+/// visitors with the `xsi` prefix, see [`canonical_attribute_name()`].
+pub fn is_reserved_attribute(name: &str) -> bool {
+    name == "xmlns" || name.starts_with("xmlns:") || name.starts_with("xsi:")
+}
+
+/// Get the name an attribute is visited with, replacing any prefix bound to the [`XSI_NAMESPACE`] by `xsi`
+///
+/// The prefix is resolved using the given namespace declarations in scope, see [`resolve_prefix()`]. This way, e.g.
+/// `i:nil` with `xmlns:i` bound to the namespace on the element or an ancestor is recognized as reserved `xsi:nil`.
+pub fn canonical_attribute_name<'a>(
+    namespaces: &[(String, String)],
+    name: &'a str,
+) -> Cow<'a, str> {
+    match name.split_once(':') {
+        Some((prefix, local)) if prefix != "xsi" && prefix != "xmlns" => {
+            match resolve_prefix(namespaces, name) {
+                (Some(XSI_NAMESPACE), _) => Cow::Owned(format!("xsi:{}", local)),
+                _ => Cow::Borrowed(name),
+            }
+        }
+        _ => Cow::Borrowed(name),
+    }
+}
+
+/// Resolve the prefix of a qualified name using the given namespace declarations
+///
+/// The declarations are `xmlns` and `xmlns:*` attributes, innermost last, like the ones in scope of an element given
+/// by [`PeekingReader::namespaces()`](crate::PeekingReader::namespaces). Returns the namespace and the local part of
+/// the name. The namespace is [`None`] if the prefix isn't declared.
+pub fn resolve_prefix<'a>(
+    namespaces: &'a [(String, String)],
+    name: &'a str,
+) -> (Option<&'a str>, &'a str) {
+    let (declaration, local) = match name.split_once(':') {
+        Some((prefix, local)) => (format!("xmlns:{}", prefix), local),
+        None => ("xmlns".to_string(), name),
+    };
+    let namespace = namespaces
+        .iter()
+        .rev()
+        .find(|(name, _)| *name == declaration)
+        .map(|(_, value)| value.as_str());
+    (namespace, local)
+}
+
+/// Expand a qualified name to `{namespace}local` using the given namespace declarations
+///
+/// Names with an undeclared prefix are returned unchanged. The reader expands the value of `xsi:type` this way, as
+/// the declarations it uses may be out of scope once the value is looked at.
+pub fn expand_qualified_name<'a>(namespaces: &[(String, String)], name: &'a str) -> Cow<'a, str> {
+    match resolve_prefix(namespaces, name) {
+        (Some(namespace), local) => Cow::Owned(format!("{{{}}}{}", namespace, local)),
+        (None, _) => Cow::Borrowed(name),
+    }
+}
+
+/// Split a name as given by [`expand_qualified_name()`] into its namespace and local part
+///
+/// The namespace is [`None`] if the name isn't expanded, in which case any unresolved prefix is dropped.
+pub fn split_expanded_name(name: &str) -> (Option<&str>, &str) {
+    match name.strip_prefix('{').and_then(|name| name.split_once('}')) {
+        Some((namespace, local)) => (Some(namespace), local),
+        None => (None, name.rsplit(':').next().unwrap_or(name)),
+    }
+}
+
+/// Find the name and value of the `xsi:type` attribute among the given attributes
+///
+/// The attribute may use any prefix bound to the [`XSI_NAMESPACE`] in the given attributes. Without such a
+/// declaration, the conventional `xsi` prefix is assumed, which is what the reader passes on for prefixes bound to
+/// the namespace anywhere in scope, see [`canonical_attribute_name()`].
+pub fn xsi_type(attributes: &[(String, String)]) -> Option<(&str, &str)> {
+    attributes
+        .iter()
+        .find(|(name, _)| match name.split_once(':') {
+            Some((prefix, "type")) => match resolve_prefix(attributes, name) {
+                (Some(namespace), _) => namespace == XSI_NAMESPACE,
+                (None, _) => prefix == "xsi",
+            },
+            _ => false,
+        })
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+}
+
+/// The buffered reader [`BufferedElement`]s are replayed from
+pub type ReplayReader = Cursor<Vec<u8>>;
+
+/// A visitor buffering all contents of an element so they can be replayed to other visitors
+///
+/// Attributes and text are stored as they are visited, child elements are stored as raw XML. This is used for
+/// untagged enums, which try to deserialize the same element with the visitors of all of their variants.
+#[derive(Debug, Clone, Default)]
+pub struct BufferedElement {
+    name: String,
+    events: Vec<BufferedEvent>,
+}
+
+#[derive(Debug, Clone)]
+enum BufferedEvent {
+    Attribute(String, String),
+    Text(String),
+    /// name, raw XML and the namespace declarations in scope of the child
+    Child(String, Vec<u8>, Vec<(String, String)>),
+}
+
+impl BufferedElement {
+    /// Tag name of the buffered element
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Replay the buffered element to a visitor without awaiting anything
+    ///
+    /// This fails if the buffered element has any child elements, use [`replay()`](Self::replay) for those.
+    pub fn replay_sync<V>(&self, mut visitor: V) -> Result<V::Output, Error>
+    where
+        V: Visitor<ReplayReader>,
+    {
+        self.replay_start(&mut visitor)?;
+        for event in &self.events {
+            match event {
+                BufferedEvent::Attribute(name, value) => visitor.visit_attribute(name, value)?,
+                BufferedEvent::Text(text) => visitor.visit_text(text)?,
+                BufferedEvent::Child(name, _, _) => {
+                    return Err(Error::Deserialization(format!(
+                        "can't replay child element <{}> without awaiting",
+                        name
+                    )))
+                }
+            }
+        }
+        visitor.build()
+    }
+
+    /// Replay the buffered element to a visitor
+    pub async fn replay<V>(&self, mut visitor: V) -> Result<V::Output, Error>
+    where
+        V: Visitor<ReplayReader>,
+    {
+        self.replay_start(&mut visitor)?;
+        for event in &self.events {
+            match event {
+                BufferedEvent::Attribute(name, value) => visitor.visit_attribute(name, value)?,
+                BufferedEvent::Text(text) => visitor.visit_text(text)?,
+                BufferedEvent::Child(name, raw, namespaces) => {
+                    let mut reader = crate::PeekingReader::from_buf(Cursor::new(raw.clone()));
+                    reader.declare_namespaces(namespaces);
+                    visitor.visit_child(name, &mut reader).await?;
+                }
+            }
+        }
+        visitor.finish().await
+    }
+
+    fn replay_start<V>(&self, visitor: &mut V) -> Result<(), Error>
+    where
+        V: Visitor<ReplayReader>,
+    {
+        // the tag is only known if the element has been read from XML, not when visiting an attribute value
+        if self.name.is_empty() {
+            return Ok(());
+        }
+        crate::reader::check_start_name::<V, ReplayReader>(&self.name)?;
+        visitor.visit_tag(&self.name)
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<B> Visitor<B> for BufferedElement
+where
+    B: AsyncBufRead + Unpin,
+{
+    type Output = Self;
+
+    fn visit_tag(&mut self, name: &str) -> Result<(), Error> {
+        self.name = name.into();
+        Ok(())
+    }
+
+    fn visit_attribute(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        self.events
+            .push(BufferedEvent::Attribute(name.into(), value.into()));
+        Ok(())
+    }
+
+    fn visit_text(&mut self, text: &str) -> Result<(), Error> {
+        self.events.push(BufferedEvent::Text(text.into()));
+        Ok(())
+    }
+
+    async fn visit_child(
+        &mut self,
+        name: &str,
+        reader: &mut crate::PeekingReader<B>,
+    ) -> Result<(), Error> {
+        // the child is replayed from its raw XML, without the declarations of its ancestors
+        let namespaces = reader.namespaces().to_vec();
+        let mut writer = quick_xml::Writer::new(Vec::new());
+        let mut depth = 0_usize;
+        loop {
+            let event = reader.read_event().await?;
+            match &event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                Event::Eof => {
+                    return Err(quick_xml::Error::UnexpectedEof(format!("</{}>", name)).into())
+                }
+                _ => {}
+            }
+            writer.write_event(&event)?;
+            if depth == 0 {
+                break;
+            }
+        }
+        self.events.push(BufferedEvent::Child(
+            name.into(),
+            writer.into_inner(),
+            namespaces,
+        ));
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Error> {
+        Ok(self)
+    }
+}
+
+impl<B> FromXml<B> for BufferedElement
+where
+    B: AsyncBufRead + Unpin,
+{
+    type Visitor = Self;
+}
+
+/// A visitor descending through an intermediate element of a nested field path like `header/buyer/id`
+///
+/// Child elements and attributes are passed back to the parent visitor with their full path as the name, e.g.
+/// `header/buyer` for a child and `header/buyer/@ref` for an attribute, so the parent handles them just like its own
+/// children and attributes, including its policy for unknown ones.
+pub struct PathVisitor<'v, V, B>
+where
+    B: AsyncBufRead + Unpin,
+    V: Visitor<B>,
+{
+    parent: &'v mut V,
+    path: &'static str,
+    allow_unknown_text: bool,
+    _phantom: core::marker::PhantomData<B>,
+}
+
+impl<'v, V, B> PathVisitor<'v, V, B>
+where
+    B: AsyncBufRead + Unpin,
+    V: Visitor<B>,
+{
+    /// Create a new [`PathVisitor`] for the element at `path` below the parent visitor's element
+    pub fn new(parent: &'v mut V, path: &'static str, allow_unknown_text: bool) -> Self {
+        Self {
+            parent,
+            path,
+            allow_unknown_text,
+            _phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<'v, V, B> Visitor<B> for PathVisitor<'v, V, B>
+where
+    B: AsyncBufRead + Unpin,
+    V: Visitor<B>,
+{
+    type Output = ();
+
+    fn visit_attribute(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        if is_reserved_attribute(name) {
+            return Ok(());
+        }
+        self.parent
+            .visit_attribute(&format!("{}/@{}", self.path, name), value)
+    }
+
+    async fn visit_child(
+        &mut self,
+        name: &str,
+        reader: &mut crate::PeekingReader<B>,
+    ) -> Result<(), Error> {
+        self.parent
+            .visit_child(&format!("{}/{}", self.path, name), reader)
+            .await
+    }
+
+    fn visit_text(&mut self, _text: &str) -> Result<(), Error> {
+        if self.allow_unknown_text {
+            Ok(())
+        } else {
+            Err(Error::UnexpectedText)
+        }
+    }
+
+    fn build(self) -> Result<Self::Output, Error> {
+        Ok(())
+    }
+}
+
+/// A visitor passing selected attributes of a child element back to the parent visitor
+///
+/// The attributes in `attributes` are passed to the parent visitor with their full path as the name, e.g.
+/// `price/@currency`, while everything else is handled by the wrapped visitor deserializing the child itself.
+pub struct ChildAttributeVisitor<'v, P, V, B>
+where
+    B: AsyncBufRead + Unpin,
+    P: Visitor<B>,
+    V: Visitor<B>,
+{
+    parent: &'v mut P,
+    inner: V,
+    path: &'v str,
+    attributes: &'static [&'static str],
+    _phantom: core::marker::PhantomData<B>,
+}
+
+impl<'v, P, V, B> ChildAttributeVisitor<'v, P, V, B>
+where
+    B: AsyncBufRead + Unpin,
+    P: Visitor<B>,
+    V: Visitor<B>,
+{
+    /// Create a new [`ChildAttributeVisitor`] for the child element at `path` below the parent visitor's element
+    pub fn new(
+        parent: &'v mut P,
+        inner: V,
+        path: &'v str,
+        attributes: &'static [&'static str],
+    ) -> Self {
+        Self {
+            parent,
+            inner,
+            path,
+            attributes,
+            _phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<'v, P, V, B> Visitor<B> for ChildAttributeVisitor<'v, P, V, B>
+where
+    B: AsyncBufRead + Unpin,
+    P: Visitor<B>,
+    V: Visitor<B>,
+{
+    type Output = V::Output;
+
+    fn start_name() -> Option<&'static str> {
+        V::start_name()
+    }
+
+    fn start_names() -> Vec<&'static str> {
+        V::start_names()
+    }
+
+    fn visit_tag(&mut self, name: &str) -> Result<(), Error> {
+        self.inner.visit_tag(name)
+    }
+
+    fn visit_attribute(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        if self.attributes.contains(&name) {
+            self.parent
+                .visit_attribute(&format!("{}/@{}", self.path, name), value)
+        } else {
+            self.inner.visit_attribute(name, value)
+        }
+    }
+
+    async fn visit_child(
+        &mut self,
+        name: &str,
+        reader: &mut crate::PeekingReader<B>,
+    ) -> Result<(), Error> {
+        self.inner.visit_child(name, reader).await
+    }
+
+    fn visit_text(&mut self, text: &str) -> Result<(), Error> {
+        self.inner.visit_text(text)
+    }
+
+    fn build(self) -> Result<Self::Output, Error> {
+        self.inner.build()
+    }
+
+    async fn finish(self) -> Result<Self::Output, Error> {
+        self.inner.finish().await
+    }
 }
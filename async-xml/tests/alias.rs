@@ -0,0 +1,86 @@
+use async_xml::{from_str, reader::PeekingReader, Error, Registry};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_field_aliases() {
+    for xml in [
+        r#"<product sku="1"><ean>4006381333931</ean></product>"#,
+        r#"<product SKU="1"><EAN>4006381333931</EAN></product>"#,
+        r#"<item sku="1"><gtin>4006381333931</gtin></item>"#,
+    ] {
+        let de: Product = from_str(xml).await.unwrap();
+        let expected = Product {
+            sku: 1,
+            ean: 4006381333931,
+            tags: Vec::new(),
+        };
+        assert_eq!(de, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_vec_aliases() {
+    let xml = r#"<product sku="1"><ean>1</ean><tag>a</tag><label>b</label></product>"#;
+    let de: Product = from_str(xml).await.unwrap();
+    assert_eq!(de.tags, vec!["a".to_string(), "b".to_string()]);
+}
+
+#[tokio::test]
+async fn test_double_alias() {
+    let xml = r#"<product sku="1"><ean>1</ean><gtin>1</gtin></product>"#;
+    let err = from_str::<Product>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::DoubleChild(name) if name == "gtin"));
+}
+
+#[tokio::test]
+async fn test_double_attribute_alias() {
+    let xml = r#"<product sku="1" SKU="2"><ean>1</ean></product>"#;
+    let err = from_str::<Product>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::DoubleAttribute(name) if name == "SKU"));
+}
+
+#[tokio::test]
+async fn test_wrong_start() {
+    let xml = r#"<article sku="1"><ean>1</ean></article>"#;
+    let err = from_str::<Product>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::WrongStart(expected, _) if expected == "product|item"));
+}
+
+#[tokio::test]
+async fn test_variant_alias() {
+    let xml = r#"<payment><card number="1" /></payment>"#;
+    let de: Payment = from_str(xml).await.unwrap();
+    assert_eq!(de, Payment::CreditCard { number: 1 });
+}
+
+#[tokio::test]
+async fn test_registry() {
+    let mut registry = Registry::new();
+    registry.register::<Product>();
+    let mut reader = PeekingReader::from_str(r#"<item sku="2"><ean>3</ean></item>"#);
+    let de = registry.deserialize(&mut reader).await.unwrap();
+    let de = de.downcast::<Product>().unwrap();
+    assert_eq!(de.sku, 2);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "product", alias = "item")]
+pub struct Product {
+    #[async_xml(attribute, alias = "SKU")]
+    sku: u32,
+    #[async_xml(child, alias = "EAN", alias = "gtin")]
+    ean: u64,
+    #[async_xml(child, rename = "tag", alias = "label")]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "payment")]
+pub enum Payment {
+    #[async_xml(rename = "credit-card", alias = "card")]
+    CreditCard {
+        #[async_xml(attribute)]
+        number: u32,
+    },
+    Cash,
+}
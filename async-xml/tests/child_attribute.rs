@@ -0,0 +1,67 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_child_attribute() {
+    let xml = r#"<item><price currency="EUR">1250</price><link href="https://example.com" rel="self"/></item>"#;
+    let de: Item = from_str(xml).await.unwrap();
+    let expected = Item {
+        price: 1250,
+        currency: "EUR".into(),
+        link: "https://example.com".into(),
+        rel: Some("self".into()),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_child_attribute_missing() {
+    let xml = r#"<item><price>1250</price><link href="https://example.com"/></item>"#;
+    let err = from_str::<Item>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::MissingAttribute(name) if name == "price/@currency"));
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "item")]
+pub struct Item {
+    #[async_xml(child)]
+    price: u32,
+    #[async_xml(child_attribute = "price@currency")]
+    currency: String,
+    #[async_xml(child_attribute = "link@href")]
+    link: String,
+    #[async_xml(child_attribute = "link@rel")]
+    rel: Option<String>,
+}
+
+#[tokio::test]
+async fn test_child_attribute_struct() {
+    let xml = r#"<entry><author id="7" role="editor"><name>Jane</name></author></entry>"#;
+    let de: Entry = from_str(xml).await.unwrap();
+    let expected = Entry {
+        author: Author {
+            id: 7,
+            name: "Jane".into(),
+        },
+        role: "editor".into(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "entry")]
+pub struct Entry {
+    #[async_xml(child)]
+    author: Author,
+    #[async_xml(child_attribute = "author@role")]
+    role: String,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "author")]
+pub struct Author {
+    #[async_xml(attribute)]
+    id: u32,
+    #[async_xml(child)]
+    name: String,
+}
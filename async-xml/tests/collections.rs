@@ -0,0 +1,104 @@
+use async_xml::{collection::XmlCollection, from_str};
+use async_xml_derive::FromXml;
+use std::{
+    collections::{BTreeSet, HashSet, VecDeque},
+    rc::Rc,
+    sync::Arc,
+};
+
+#[tokio::test]
+async fn test_recursive_box() {
+    let xml = r#"<node name="root"><node name="a"><node name="b"/></node></node>"#;
+    let de: Node = from_str(xml).await.unwrap();
+    let expected = Node {
+        name: "root".into(),
+        next: Some(Box::new(Node {
+            name: "a".into(),
+            next: Some(Box::new(Node {
+                name: "b".into(),
+                next: None,
+            })),
+        })),
+    };
+    assert_eq!(de, expected);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "node")]
+pub struct Node {
+    #[async_xml(attribute)]
+    name: String,
+    #[async_xml(child, rename = "node")]
+    next: Option<Box<Node>>,
+}
+
+#[tokio::test]
+async fn test_pointers() {
+    let xml = r#"<pointers label="shared"><point x="1"/><n>2</n><n>3</n>text</pointers>"#;
+    let de: Pointers = from_str(xml).await.unwrap();
+    let expected = Pointers {
+        label: "shared".into(),
+        first: Rc::new(Point { x: 1 }),
+        rest: vec![Arc::new(2), Arc::new(3)],
+        text: "text".into(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "pointers")]
+pub struct Pointers {
+    #[async_xml(attribute)]
+    label: Arc<str>,
+    #[async_xml(child, rename = "point")]
+    first: Rc<Point>,
+    #[async_xml(child, rename = "n")]
+    rest: std::vec::Vec<Arc<u32>>,
+    #[async_xml(value)]
+    text: Box<str>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "point")]
+pub struct Point {
+    #[async_xml(attribute)]
+    x: u32,
+}
+
+#[tokio::test]
+async fn test_collections() {
+    let xml = r#"<collections><queue>2</queue><queue>1</queue><set>3</set><set>1</set><set>3</set><hash>4</hash><stack>5</stack><stack>6</stack><node>any</node></collections>"#;
+    let de: Collections = from_str(xml).await.unwrap();
+    assert_eq!(de.queue, VecDeque::from(vec![2, 1]));
+    assert_eq!(de.set, BTreeSet::from([1, 3]));
+    assert_eq!(de.hash, HashSet::from([4]));
+    assert_eq!(de.stack.0, vec![6, 5]);
+    assert_eq!(de.node.text, Some("any".into()));
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "collections")]
+pub struct Collections {
+    #[async_xml(child)]
+    queue: VecDeque<u32>,
+    #[async_xml(child)]
+    set: std::collections::BTreeSet<u32>,
+    #[async_xml(child)]
+    hash: HashSet<u32>,
+    #[async_xml(child, collection)]
+    stack: Stack,
+    #[async_xml(child)]
+    node: async_xml::util::XmlNode,
+}
+
+/// custom collection pushing items to the front
+#[derive(Debug, Default, PartialEq)]
+pub struct Stack(Vec<u32>);
+
+impl XmlCollection for Stack {
+    type Item = u32;
+
+    fn push_item(&mut self, item: Self::Item) {
+        self.0.insert(0, item);
+    }
+}
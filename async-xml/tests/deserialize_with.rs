@@ -0,0 +1,103 @@
+use async_xml::{from_str, reader::PeekingReader, Error};
+use async_xml_derive::FromXml;
+use tokio::io::AsyncBufRead;
+
+#[tokio::test]
+async fn test_deserialize_with() {
+    let xml = r#"<invoice total="12,50" due="31.01.2023"><line>1,5</line><line>2</line><note>paid</note>42,00</invoice>"#;
+    let de: Invoice = from_str(xml).await.unwrap();
+    let expected = Invoice {
+        total: 1250,
+        due: Some(Date {
+            day: 31,
+            month: 1,
+            year: 2023,
+        }),
+        lines: vec![150, 200],
+        note: "PAID".into(),
+        paid: 4200,
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_optional() {
+    let xml = r#"<invoice total="1"><note>open</note>0</invoice>"#;
+    let de: Invoice = from_str(xml).await.unwrap();
+    assert_eq!(de.due, None);
+    assert_eq!(de.total, 100);
+}
+
+#[tokio::test]
+async fn test_error() {
+    let xml = r#"<invoice total="1" due="tomorrow"><note>open</note>0</invoice>"#;
+    let err = from_str::<Invoice>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::Deserialization(_)));
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "invoice")]
+pub struct Invoice {
+    #[async_xml(attribute, deserialize_with = "parse_cents")]
+    total: u64,
+    #[async_xml(attribute, with = "german_date", default)]
+    due: Option<Date>,
+    #[async_xml(child, rename = "line", deserialize_with = "child_cents")]
+    lines: Vec<u64>,
+    #[async_xml(child, deserialize_with = "upper_child")]
+    note: String,
+    #[async_xml(value, deserialize_with = "parse_cents")]
+    paid: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Date {
+    day: u8,
+    month: u8,
+    year: u16,
+}
+
+fn parse_cents(s: &str) -> Result<u64, Error> {
+    let invalid = || Error::Deserialization(format!("invalid amount {}", s));
+    let (units, cents) = s.split_once(',').unwrap_or((s, "0"));
+    let cents = format!("{:0<2}", cents);
+    let units: u64 = units.parse().map_err(|_| invalid())?;
+    let cents: u64 = cents.parse().map_err(|_| invalid())?;
+    Ok(units * 100 + cents)
+}
+
+async fn child_cents<B: AsyncBufRead + Unpin>(reader: &mut PeekingReader<B>) -> Result<u64, Error> {
+    let text: String = reader.deserialize().await?;
+    parse_cents(&text)
+}
+
+async fn upper_child<B: AsyncBufRead + Unpin>(
+    reader: &mut PeekingReader<B>,
+) -> Result<String, Error> {
+    let text: String = reader.deserialize().await?;
+    Ok(text.to_uppercase())
+}
+
+mod german_date {
+    use super::Date;
+    use async_xml::Error;
+
+    pub fn deserialize(s: &str) -> Result<Option<Date>, Error> {
+        let invalid = || Error::Deserialization(format!("invalid date {}", s));
+        let mut parts = s.split('.');
+        let mut next = || {
+            parts
+                .next()
+                .and_then(|p| p.parse().ok())
+                .ok_or_else(invalid)
+        };
+        let day = next()?;
+        let month = next()?;
+        let year = next()?;
+        Ok(Some(Date {
+            day: day as u8,
+            month: month as u8,
+            year,
+        }))
+    }
+}
@@ -0,0 +1,116 @@
+use async_xml::from_str;
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_newtype() {
+    let xml = r#"<payment><card number="1234" /></payment>"#;
+    let de: Payment = from_str(xml).await.unwrap();
+    let expected = Payment::Card(Card {
+        number: "1234".into(),
+    });
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_struct() {
+    let xml = r#"<payment><paypal email="a@b.c"><note>thanks</note></paypal></payment>"#;
+    let de: Payment = from_str(xml).await.unwrap();
+    let expected = Payment::Paypal {
+        email: "a@b.c".into(),
+        note: Some("thanks".into()),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_unit() {
+    let xml = r#"<payment><cash /></payment>"#;
+    let de: Payment = from_str(xml).await.unwrap();
+    assert_eq!(de, Payment::Cash);
+}
+
+#[tokio::test]
+async fn test_unit_only() {
+    let xml = r#"<delivery><pickup /></delivery>"#;
+    let de: Delivery = from_str(xml).await.unwrap();
+    assert_eq!(de, Delivery::Pickup);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_unit_only_text() {
+    let xml = r#"<delivery>pickup</delivery>"#;
+    let _: Delivery = from_str(xml).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_child() {
+    let xml = r#"<order id="1"><payment><Invoice>42</Invoice></payment></order>"#;
+    let de: Order = from_str(xml).await.unwrap();
+    let expected = Order {
+        id: 1,
+        payment: Payment::Invoice(42),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_missing() {
+    let xml = r#"<payment></payment>"#;
+    let _: Payment = from_str(xml).await.unwrap();
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_multiple() {
+    let xml = r#"<payment><cash /><Invoice>42</Invoice></payment>"#;
+    let _: Payment = from_str(xml).await.unwrap();
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_unknown() {
+    let xml = r#"<payment><cheque /></payment>"#;
+    let _: Payment = from_str(xml).await.unwrap();
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "order")]
+pub struct Order {
+    #[async_xml(attribute)]
+    id: u32,
+    #[async_xml(child)]
+    payment: Payment,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "payment")]
+pub enum Payment {
+    #[async_xml(rename = "card")]
+    Card(Card),
+    Invoice(u32),
+    #[async_xml(rename = "paypal")]
+    Paypal {
+        #[async_xml(attribute)]
+        email: String,
+        #[async_xml(child)]
+        note: Option<String>,
+    },
+    #[async_xml(rename = "cash")]
+    Cash,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "card")]
+pub struct Card {
+    #[async_xml(attribute)]
+    number: String,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "delivery", rename_all = "lowercase")]
+pub enum Delivery {
+    Pickup,
+    Courier,
+}
@@ -0,0 +1,90 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_newtype() {
+    let xml = r#"<shipment id="1" type="parcel"><weight>3</weight></shipment>"#;
+    let de: Shipment = from_str(xml).await.unwrap();
+    let expected = Shipment::Parcel(Parcel { id: 1, weight: 3 });
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_struct() {
+    let xml = r#"<shipment type="letter" stamp="true">Hello</shipment>"#;
+    let de: Shipment = from_str(xml).await.unwrap();
+    let expected = Shipment::Letter {
+        stamp: true,
+        text: "Hello".into(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_unit() {
+    let xml = r#"<shipment type="pickup" />"#;
+    let de: Shipment = from_str(xml).await.unwrap();
+    assert_eq!(de, Shipment::Pickup);
+}
+
+#[tokio::test]
+async fn test_child() {
+    let xml = r#"<order><shipment type="pickup" /></order>"#;
+    let de: Order = from_str(xml).await.unwrap();
+    let expected = Order {
+        shipment: Shipment::Pickup,
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_missing_tag() {
+    let xml = r#"<shipment><weight>3</weight></shipment>"#;
+    let de: Result<Shipment, _> = from_str(xml).await;
+    assert!(matches!(de, Err(Error::MissingAttribute(a)) if a == "type"));
+}
+
+#[tokio::test]
+async fn test_unknown_tag() {
+    let xml = r#"<shipment type="pallet" />"#;
+    let de: Result<Shipment, _> = from_str(xml).await;
+    assert!(matches!(de, Err(Error::UnknownVariant(v)) if v == "pallet"));
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_unexpected_attribute() {
+    let xml = r#"<shipment type="pickup" id="1" />"#;
+    let _: Shipment = from_str(xml).await.unwrap();
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "order")]
+pub struct Order {
+    #[async_xml(child)]
+    shipment: Shipment,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "shipment", tag_attribute = "type")]
+pub enum Shipment {
+    #[async_xml(rename = "parcel")]
+    Parcel(Parcel),
+    #[async_xml(rename = "letter")]
+    Letter {
+        #[async_xml(attribute)]
+        stamp: bool,
+        #[async_xml(value)]
+        text: String,
+    },
+    #[async_xml(rename = "pickup")]
+    Pickup,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+pub struct Parcel {
+    #[async_xml(attribute)]
+    id: u32,
+    #[async_xml(child)]
+    weight: u32,
+}
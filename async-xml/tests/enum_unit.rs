@@ -0,0 +1,95 @@
+use async_xml::from_str;
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_value() {
+    let xml = r#"<status>SHIPPED</status>"#;
+    let de: Status = from_str(xml).await.unwrap();
+    assert_eq!(de, Status::Shipped);
+}
+
+#[tokio::test]
+async fn test_alias() {
+    let xml = r#"<status>SENT</status>"#;
+    let de: Status = from_str(xml).await.unwrap();
+    assert_eq!(de, Status::Shipped);
+}
+
+#[tokio::test]
+async fn test_case_insensitive_variant() {
+    let xml = r#"<status>Pending</status>"#;
+    let de: Status = from_str(xml).await.unwrap();
+    assert_eq!(de, Status::Pending);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_case_sensitive() {
+    let xml = r#"<status>shipped</status>"#;
+    let _: Status = from_str(xml).await.unwrap();
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_unknown() {
+    let xml = r#"<status>LOST</status>"#;
+    let _: Status = from_str(xml).await.unwrap();
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_missing() {
+    let xml = r#"<status />"#;
+    let _: Status = from_str(xml).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_attribute_and_child() {
+    let xml = r#"<ticket state="Closed"><status>DELIVERED</status></ticket>"#;
+    let de: Ticket = from_str(xml).await.unwrap();
+    let expected = Ticket {
+        state: State::Closed,
+        status: Status::Delivered,
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_other() {
+    let xml = r#"<ticket state="reopened"><status>PENDING</status></ticket>"#;
+    let de: Ticket = from_str(xml).await.unwrap();
+    let expected = Ticket {
+        state: State::Other("reopened".into()),
+        status: Status::Pending,
+    };
+    assert_eq!(de, expected);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "status", text)]
+pub enum Status {
+    #[async_xml(rename = "PENDING", case_insensitive)]
+    Pending,
+    #[async_xml(rename = "SHIPPED", alias = "SENT")]
+    Shipped,
+    #[async_xml(rename = "DELIVERED")]
+    Delivered,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(text, case_insensitive)]
+pub enum State {
+    Open,
+    Closed,
+    #[async_xml(other)]
+    Other(String),
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "ticket")]
+pub struct Ticket {
+    #[async_xml(attribute)]
+    state: State,
+    #[async_xml(child)]
+    status: Status,
+}
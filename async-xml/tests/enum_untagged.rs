@@ -0,0 +1,108 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_simple() {
+    let xml = r#"<price>1250</price>"#;
+    let de: Price = from_str(xml).await.unwrap();
+    assert_eq!(de, Price::Simple(1250));
+}
+
+#[tokio::test]
+async fn test_detailed() {
+    let xml = r#"<price><amount>1250</amount><currency>EUR</currency></price>"#;
+    let de: Price = from_str(xml).await.unwrap();
+    let expected = Price::Detailed {
+        amount: 1250,
+        currency: "EUR".into(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_newtype_struct() {
+    let xml = r#"<price free="true" />"#;
+    let de: Price = from_str(xml).await.unwrap();
+    assert_eq!(de, Price::Free(Free { free: true }));
+}
+
+#[tokio::test]
+async fn test_child() {
+    let xml =
+        r#"<item><price>3</price><price><amount>4</amount><currency>USD</currency></price></item>"#;
+    let de: Item = from_str(xml).await.unwrap();
+    let expected = Item {
+        prices: vec![
+            Price::Simple(3),
+            Price::Detailed {
+                amount: 4,
+                currency: "USD".into(),
+            },
+        ],
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_attribute() {
+    let xml = r#"<discount value="5" />"#;
+    let de: Discount = from_str(xml).await.unwrap();
+    assert_eq!(de.value, Amount::Number(5));
+    let xml = r#"<discount value="half" />"#;
+    let de: Discount = from_str(xml).await.unwrap();
+    assert_eq!(de.value, Amount::Text("half".into()));
+}
+
+#[tokio::test]
+async fn test_no_match() {
+    let xml = r#"<price><amount>1250</amount></price>"#;
+    let err = from_str::<Price>(xml).await.unwrap_err();
+    match err {
+        Error::NoMatchingVariant(errors) => {
+            let variants = errors.iter().map(|(v, _)| v.as_str()).collect::<Vec<_>>();
+            assert_eq!(variants, vec!["Simple", "Detailed", "Free"]);
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "price", untagged)]
+pub enum Price {
+    Simple(u32),
+    Detailed {
+        #[async_xml(child)]
+        amount: u32,
+        #[async_xml(child)]
+        currency: String,
+    },
+    Free(Free),
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "price")]
+pub struct Free {
+    #[async_xml(attribute)]
+    free: bool,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "item")]
+pub struct Item {
+    #[async_xml(child, rename = "price")]
+    prices: Vec<Price>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(untagged)]
+pub enum Amount {
+    Number(u32),
+    Text(String),
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "discount")]
+pub struct Discount {
+    #[async_xml(attribute)]
+    value: Amount,
+}
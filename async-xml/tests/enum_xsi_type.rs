@@ -0,0 +1,150 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_prefixed_value() {
+    let xml = r#"<address xsi:type="ns:ExtendedAddress" xmlns:ns="urn:example"><street>Main St</street><floor>3</floor></address>"#;
+    let de: Address = from_str(xml).await.unwrap();
+    let expected = Address::Extended(ExtendedAddress {
+        street: "Main St".into(),
+        floor: 3,
+    });
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_undeclared_prefix() {
+    // prefixes that aren't declared at all can't be resolved, so only the local name is used
+    let xml = r#"<address xsi:type="ns:BaseAddress"><street>Main St</street></address>"#;
+    let de: Address = from_str(xml).await.unwrap();
+    let expected = Address::Base {
+        street: "Main St".into(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_qualified_variant_name() {
+    let xml = r#"<address xmlns:i="http://www.w3.org/2001/XMLSchema-instance" i:type="p:Box" xmlns:p="urn:post"><number>12</number></address>"#;
+    let de: Address = from_str(xml).await.unwrap();
+    assert_eq!(de, Address::PostBox { number: 12 });
+}
+
+#[tokio::test]
+async fn test_other_namespace() {
+    // the variant only matches the qualified name in the urn:post namespace
+    let xml = r#"<address xsi:type="q:Box" xmlns:q="urn:other"><number>12</number></address>"#;
+    let err = from_str::<Address>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnknownVariant(v) if v == "{urn:other}Box"));
+}
+
+#[tokio::test]
+async fn test_ancestor_declarations() {
+    let xml = r#"<envelope xmlns:i="http://www.w3.org/2001/XMLSchema-instance" xmlns:p="urn:post"><address i:type="p:Box"><number>12</number></address></envelope>"#;
+    let de: Envelope = from_str(xml).await.unwrap();
+    assert_eq!(de.addresses, vec![Address::PostBox { number: 12 }]);
+}
+
+#[tokio::test]
+async fn test_declarations_out_of_scope() {
+    // the prefix declared on the first address isn't in scope for its sibling
+    let xml = r#"<envelope><address xmlns:p="urn:post" xsi:type="p:Box"><number>1</number></address><address xsi:type="p:Box"><number>2</number></address></envelope>"#;
+    let err = from_str::<Envelope>(xml).await.unwrap_err();
+    match err {
+        Error::InnerDeserialiaztionError(name, e) => {
+            assert_eq!(name, "address");
+            assert!(matches!(*e, Error::UnknownVariant(v) if v == "p:Box"));
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_missing_type() {
+    let xml = r#"<address><street>Main St</street></address>"#;
+    let err = from_str::<Address>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::MissingAttribute(a) if a == "xsi:type"));
+}
+
+#[tokio::test]
+async fn test_reserved_attributes() {
+    let xml = r#"<contact xmlns="urn:example" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="urn:example contact.xsd"><name xsi:nil="false">Bob</name></contact>"#;
+    let de: Contact = from_str(xml).await.unwrap();
+    assert_eq!(de.name, "Bob");
+}
+
+#[tokio::test]
+async fn test_reserved_attributes_prefix() {
+    let xml = r#"<contact xmlns:i="http://www.w3.org/2001/XMLSchema-instance" i:schemaLocation="urn:example contact.xsd"><name xmlns:i="http://www.w3.org/2001/XMLSchema-instance" i:nil="false">Bob</name></contact>"#;
+    let de: Contact = from_str(xml).await.unwrap();
+    assert_eq!(de.name, "Bob");
+}
+
+#[tokio::test]
+async fn test_reserved_attributes_ancestor_prefix() {
+    let xml = r#"<people xmlns:i="http://www.w3.org/2001/XMLSchema-instance"><contact i:nil="false"><name i:nil="false">Bob</name></contact></people>"#;
+    let de: People = from_str(xml).await.unwrap();
+    assert_eq!(de.contacts[0].name, "Bob");
+}
+
+#[tokio::test]
+async fn test_unbound_prefix() {
+    // only prefixes bound to the schema instance namespace are reserved
+    let xml = r#"<contact xmlns:i="urn:other" i:nil="false"><name>Bob</name></contact>"#;
+    let err = from_str::<Contact>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnexpectedAttribute(a) if a == "i:nil"));
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_unexpected_attribute() {
+    let xml = r#"<contact id="1"><name>Bob</name></contact>"#;
+    let _: Contact = from_str(xml).await.unwrap();
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "address", xsi_type)]
+pub enum Address {
+    #[async_xml(rename = "BaseAddress")]
+    Base {
+        #[async_xml(child)]
+        street: String,
+    },
+    #[async_xml(rename = "ExtendedAddress")]
+    Extended(ExtendedAddress),
+    #[async_xml(rename = "{urn:post}Box")]
+    PostBox {
+        #[async_xml(child)]
+        number: u32,
+    },
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "address")]
+pub struct ExtendedAddress {
+    #[async_xml(child)]
+    street: String,
+    #[async_xml(child)]
+    floor: u32,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "contact")]
+pub struct Contact {
+    #[async_xml(child)]
+    name: String,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "envelope")]
+pub struct Envelope {
+    #[async_xml(child, rename = "address")]
+    addresses: Vec<Address>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "people")]
+pub struct People {
+    #[async_xml(child, rename = "contact")]
+    contacts: Vec<Contact>,
+}
@@ -0,0 +1,179 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+use std::{convert::TryFrom, net::IpAddr, str::FromStr};
+
+#[tokio::test]
+async fn test_from_str() {
+    let xml = r#"<host addr="10.0.0.1" fallback="::1"><color>#ff8000</color><alias>a.example</alias><alias>b.example</alias><mirror>m.example</mirror>#000000</host>"#;
+    let de: Host = from_str(xml).await.unwrap();
+    let expected = Host {
+        addr: "10.0.0.1".parse().unwrap(),
+        fallback: Some("::1".parse().unwrap()),
+        color: Color(255, 128, 0),
+        aliases: vec![Hostname("a.example".into()), Hostname("b.example".into())],
+        mirror: Some(Hostname("m.example".into())),
+        background: Color(0, 0, 0),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_from_str_error() {
+    let xml = r#"<host addr="10.0.0.1"><color>orange</color>#000000</host>"#;
+    let err = from_str::<Host>(xml).await.unwrap_err();
+    match err {
+        Error::InnerDeserialiaztionError(name, e) => {
+            assert_eq!(name, "color");
+            assert!(matches!(*e, Error::Deserialization(_)));
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_try_from() {
+    let xml = r#"<slot><port>8080</port><weekday>2</weekday></slot>"#;
+    let de: Slot = from_str(xml).await.unwrap();
+    let expected = Slot {
+        port: Port(8080),
+        weekday: Weekday(2),
+        fallback: None,
+        closed: None,
+        extra: vec![],
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_try_from_option_vec() {
+    let xml = r#"<slot closed="6"><port>8080</port><weekday>2</weekday><fallback>8081</fallback><extra>9000</extra><extra>9001</extra></slot>"#;
+    let de: Slot = from_str(xml).await.unwrap();
+    let expected = Slot {
+        port: Port(8080),
+        weekday: Weekday(2),
+        fallback: Some(Port(8081)),
+        closed: Some(Weekday(6)),
+        extra: vec![Port(9000), Port(9001)],
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_try_from_option_vec_error() {
+    let xml = r#"<slot><port>8080</port><weekday>2</weekday><fallback>0</fallback></slot>"#;
+    let err = from_str::<Slot>(xml).await.unwrap_err();
+    assert_conversion_error(err, "fallback");
+
+    let xml =
+        r#"<slot><port>8080</port><weekday>2</weekday><extra>9000</extra><extra>0</extra></slot>"#;
+    let err = from_str::<Slot>(xml).await.unwrap_err();
+    assert_conversion_error(err, "extra");
+}
+
+#[tokio::test]
+async fn test_try_from_error() {
+    let xml = r#"<slot><port>0</port><weekday>2</weekday></slot>"#;
+    let err = from_str::<Slot>(xml).await.unwrap_err();
+    assert_conversion_error(err, "port");
+}
+
+fn assert_conversion_error(err: Error, child: &str) {
+    match err {
+        Error::InnerDeserialiaztionError(name, e) => {
+            assert_eq!(name, child);
+            assert!(
+                matches!(*e, Error::Deserialization(msg) if msg == "error converting: port must not be 0")
+            );
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "host")]
+pub struct Host {
+    #[async_xml(attribute, from_str)]
+    addr: IpAddr,
+    #[async_xml(attribute, from_str, default)]
+    fallback: Option<IpAddr>,
+    #[async_xml(child, from_str)]
+    color: Color,
+    #[async_xml(child, from_str, rename = "alias")]
+    aliases: Vec<Hostname>,
+    #[async_xml(child, from_str, default)]
+    mirror: Option<Hostname>,
+    #[async_xml(value, from_str)]
+    background: Color,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Color(u8, u8, u8);
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6)
+            .ok_or_else(|| format!("invalid color {}", s))?;
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string());
+        Ok(Self(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Hostname(String);
+
+impl FromStr for Hostname {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.into()))
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "slot")]
+pub struct Slot {
+    #[async_xml(child, try_from = "u16")]
+    port: Port,
+    #[async_xml(child, try_from = "u8")]
+    weekday: Weekday,
+    #[async_xml(child, try_from = "u16", default)]
+    fallback: Option<Port>,
+    #[async_xml(attribute, try_from = "u8", default)]
+    closed: Option<Weekday>,
+    #[async_xml(child, try_from = "Vec<u16>", default)]
+    extra: Vec<Port>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Port(u16);
+
+impl TryFrom<u16> for Port {
+    type Error = &'static str;
+
+    fn try_from(port: u16) -> Result<Self, Self::Error> {
+        if port == 0 {
+            Err("port must not be 0")
+        } else {
+            Ok(Self(port))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Weekday(u8);
+
+impl TryFrom<u8> for Weekday {
+    type Error = String;
+
+    fn try_from(day: u8) -> Result<Self, Self::Error> {
+        if day < 7 {
+            Ok(Self(day))
+        } else {
+            Err(format!("invalid weekday {}", day))
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_finish() {
+    let xml = r#"<order customer="2"><line product="1" /><line product="3" /></order>"#;
+    let de: Order = from_str(xml).await.unwrap();
+    let expected = Order {
+        customer: Customer {
+            id: 2,
+            name: Some("Bob".into()),
+        },
+        lines: vec![
+            Line {
+                product: 1,
+                name: Some("apple".into()),
+            },
+            Line {
+                product: 3,
+                name: Some("cherry".into()),
+            },
+        ],
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_finish_error() {
+    let xml = r#"<order customer="2"><line product="5" /></order>"#;
+    let _: Order = from_str(xml).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_finish_optional() {
+    let xml = r#"<wrapper><line product="1" /></wrapper>"#;
+    let de: Wrapper = from_str(xml).await.unwrap();
+    let expected = Wrapper {
+        line: Some(Line {
+            product: 1,
+            name: Some("apple".into()),
+        }),
+    };
+    assert_eq!(de, expected);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "order")]
+pub struct Order {
+    #[async_xml(flatten)]
+    customer: Customer,
+    #[async_xml(child, rename = "line")]
+    lines: Vec<Line>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(finish = "resolve_customer")]
+pub struct Customer {
+    #[async_xml(attribute, rename = "customer")]
+    id: u32,
+    #[async_xml(attribute, default)]
+    name: Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "line", finish = "resolve_line")]
+pub struct Line {
+    #[async_xml(attribute)]
+    product: u32,
+    #[async_xml(attribute, default)]
+    name: Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "wrapper")]
+pub struct Wrapper {
+    #[async_xml(child)]
+    line: Option<Line>,
+}
+
+async fn lookup(table: &[&str], id: u32) -> Result<String, Error> {
+    tokio::task::yield_now().await;
+    table
+        .get(id as usize)
+        .map(|s| s.to_string())
+        .ok_or_else(|| Error::Deserialization(format!("unknown id {}", id)))
+}
+
+async fn resolve_customer(mut customer: Customer) -> Result<Customer, Error> {
+    customer.name = Some(lookup(&["Alice", "Eve", "Bob"], customer.id).await?);
+    Ok(customer)
+}
+
+async fn resolve_line(mut line: Line) -> Result<Line, Error> {
+    line.name = Some(lookup(&["", "apple", "banana", "cherry"], line.product).await?);
+    Ok(line)
+}
@@ -0,0 +1,105 @@
+use async_xml::{from_str, reader::XmlFromStr};
+use async_xml_derive::FromXml;
+use std::{borrow::Cow, convert::Infallible, fmt::Debug, str::FromStr};
+
+#[tokio::test]
+async fn test_type_parameter() {
+    let xml = r#"<envelope version="2"><body id="7">payload</body></envelope>"#;
+    let de: Envelope<Body> = from_str(xml).await.unwrap();
+    let expected = Envelope {
+        version: 2,
+        body: Body {
+            id: 7,
+            text: "payload".into(),
+        },
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_nested_type_parameters() {
+    let xml = r#"<batch><item>1</item><item>2</item><label>numbers</label></batch>"#;
+    let de: Batch<u32, String> = from_str(xml).await.unwrap();
+    let expected = Batch {
+        items: vec![1, 2],
+        label: Some("numbers".into()),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_lifetime() {
+    let xml = r#"<note lang="en">Hello</note>"#;
+    let de: Note<'static, String> = from_str(xml).await.unwrap();
+    assert_eq!(de.lang.0, "en");
+    assert_eq!(de.text, "Hello");
+}
+
+#[tokio::test]
+async fn test_bound() {
+    let xml = r#"<list values="1 2 3" />"#;
+    let de: List<u8> = from_str(xml).await.unwrap();
+    assert_eq!(*de.values, vec![1, 2, 3]);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "envelope")]
+pub struct Envelope<T> {
+    #[async_xml(attribute)]
+    version: u32,
+    #[async_xml(child)]
+    body: T,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "body")]
+pub struct Body {
+    #[async_xml(attribute)]
+    id: u32,
+    #[async_xml(value)]
+    text: String,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "batch")]
+pub struct Batch<T, L = String>
+where
+    T: Debug,
+{
+    #[async_xml(child, rename = "item")]
+    items: Vec<T>,
+    #[async_xml(child)]
+    label: Option<L>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "note")]
+pub struct Note<'a, T> {
+    #[async_xml(attribute)]
+    lang: Lang<'a>,
+    #[async_xml(value)]
+    text: T,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Lang<'a>(Cow<'a, str>);
+
+impl<'a> FromStr for Lang<'a> {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(Cow::Owned(s.into())))
+    }
+}
+
+impl<'a> XmlFromStr for Lang<'a> {}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(
+    rename = "list",
+    bound = "T: FromStr, <T as FromStr>::Err: std::fmt::Display"
+)]
+pub struct List<T> {
+    #[async_xml(attribute)]
+    values: async_xml::XmlVec<T>,
+}
@@ -0,0 +1,142 @@
+use async_xml::{
+    handler::{Handler, StartElement},
+    Error, PeekingReader,
+};
+use async_xml_derive::FromXml;
+use tokio::io::AsyncBufRead;
+
+#[tokio::test]
+async fn test_events() {
+    let xml = r#"<order id="1"><header><buyer>42</buyer></header>text<item /></order>"#;
+    let mut reader = PeekingReader::from_str(xml);
+    let mut handler = Recorder::default();
+    reader.handle(&mut handler).await.unwrap();
+    assert_eq!(
+        handler.events,
+        vec![
+            "start order",
+            "attribute order id=1",
+            "start order/header",
+            "start order/header/buyer",
+            "text order/header/buyer 42",
+            "end order/header/buyer",
+            "end order/header",
+            "text order text",
+            "start order/item",
+            "end order/item",
+            "end order",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_prolog() {
+    let xml =
+        r#"<?xml version="1.0"?><!DOCTYPE order><!-- header --><order id="1"><item /></order>"#;
+    let mut reader = PeekingReader::from_str(xml);
+    let mut handler = Recorder::default();
+    reader.handle(&mut handler).await.unwrap();
+    assert_eq!(
+        handler.events,
+        vec![
+            "start order",
+            "attribute order id=1",
+            "start order/item",
+            "end order/item",
+            "end order",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_deserialize_subtree() {
+    let xml = r#"<order><line sku="a">2</line><note>skip me</note><line sku="b">5</line></order>"#;
+    let mut reader = PeekingReader::from_str(xml);
+    let mut handler = Lines::default();
+    reader.handle(&mut handler).await.unwrap();
+    assert_eq!(
+        handler.lines,
+        vec![
+            Line {
+                sku: "a".into(),
+                amount: 2
+            },
+            Line {
+                sku: "b".into(),
+                amount: 5
+            },
+        ]
+    );
+    assert_eq!(handler.ends, vec!["order"]);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_wrong_end() {
+    let xml = r#"<order><item></order>"#;
+    let mut reader = PeekingReader::from_str(xml);
+    reader.handle(&mut Recorder::default()).await.unwrap();
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl<B: AsyncBufRead + Unpin> Handler<B> for Recorder {
+    async fn start_element(&mut self, element: &mut StartElement<'_, B>) -> Result<(), Error> {
+        self.events
+            .push(format!("start {}", element.path().join("/")));
+        Ok(())
+    }
+
+    fn attribute(&mut self, path: &[String], name: &str, value: &str) -> Result<(), Error> {
+        self.events
+            .push(format!("attribute {} {}={}", path.join("/"), name, value));
+        Ok(())
+    }
+
+    fn text(&mut self, path: &[String], text: &str) -> Result<(), Error> {
+        self.events
+            .push(format!("text {} {}", path.join("/"), text));
+        Ok(())
+    }
+
+    fn end_element(&mut self, path: &[String]) -> Result<(), Error> {
+        self.events.push(format!("end {}", path.join("/")));
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct Lines {
+    lines: Vec<Line>,
+    ends: Vec<String>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl<B: AsyncBufRead + Unpin> Handler<B> for Lines {
+    async fn start_element(&mut self, element: &mut StartElement<'_, B>) -> Result<(), Error> {
+        match element.name() {
+            "line" => self.lines.push(element.deserialize().await?),
+            "note" => element.skip().await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn end_element(&mut self, path: &[String]) -> Result<(), Error> {
+        self.ends.push(path.join("/"));
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "line")]
+pub struct Line {
+    #[async_xml(attribute)]
+    sku: String,
+    #[async_xml(value)]
+    amount: u32,
+}
@@ -0,0 +1,155 @@
+use async_xml::{from_str, util::XmlNode, Error};
+use async_xml_derive::FromXml;
+use std::collections::{BTreeMap, HashMap};
+
+#[tokio::test]
+async fn test_key_attribute() {
+    let xml = r#"<product><attr name="color">red</attr><attr name="size">XL</attr></product>"#;
+    let de: Product = from_str(xml).await.unwrap();
+    let mut expected = HashMap::new();
+    expected.insert("color".to_string(), "red".to_string());
+    expected.insert("size".to_string(), "XL".to_string());
+    assert_eq!(de.attributes, expected);
+}
+
+#[tokio::test]
+async fn test_key_attribute_missing() {
+    let xml = r#"<product><attr>red</attr></product>"#;
+    let err = from_str::<Product>(xml).await.unwrap_err();
+    match err {
+        Error::InnerDeserialiaztionError(name, e) => {
+            assert_eq!(name, "attr");
+            assert!(matches!(*e, Error::MissingAttribute(attr) if attr == "name"));
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_duplicate_keys_replace() {
+    let xml = r#"<product><attr name="color">red</attr><attr name="color">blue</attr></product>"#;
+    let de: Product = from_str(xml).await.unwrap();
+    assert_eq!(de.attributes["color"], "blue");
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "product")]
+pub struct Product {
+    #[async_xml(child = "attr", key = "@name")]
+    attributes: HashMap<String, String>,
+}
+
+#[tokio::test]
+async fn test_key_tag() {
+    let xml = r#"<limits id="1"><max>10</max><min>2</min><name>test</name></limits>"#;
+    let de: Limits = from_str(xml).await.unwrap();
+    let expected = Limits {
+        id: 1,
+        name: "test".into(),
+        values: BTreeMap::from([("max".into(), 10), ("min".into(), 2)]),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_duplicate_keys_denied() {
+    let xml = r#"<limits id="1"><max>10</max><max>12</max><name>test</name></limits>"#;
+    let err = from_str::<Limits>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::DuplicateKey(key) if key == "max"));
+}
+
+#[tokio::test]
+async fn test_key_tag_with_flatten_and_remains() {
+    let xml =
+        r#"<settings mode="fast" extra="1"><timeout>10</timeout><retries>3</retries></settings>"#;
+    let de: Settings = from_str(xml).await.unwrap();
+    assert_eq!(de.general, General { timeout: 10 });
+    assert_eq!(de.values, BTreeMap::from([("retries".into(), 3)]));
+    assert_eq!(de.remains.attributes.len(), 2);
+    assert!(de.remains.children.is_empty());
+}
+
+#[tokio::test]
+async fn test_key_tag_ordered() {
+    let xml = r#"<ordered_limits><name>test</name><max>10</max></ordered_limits>"#;
+    let de: OrderedLimits = from_str(xml).await.unwrap();
+    assert_eq!(de.values, BTreeMap::from([("max".into(), 10)]));
+
+    let xml = r#"<ordered_limits><max>10</max><name>test</name></ordered_limits>"#;
+    let err = from_str::<OrderedLimits>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnexpectedOrder(name) if name == "name"));
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "settings")]
+pub struct Settings {
+    #[async_xml(flatten)]
+    general: General,
+    #[async_xml(child, key = "tag")]
+    values: BTreeMap<String, u32>,
+    #[async_xml(remains)]
+    remains: XmlNode,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+pub struct General {
+    #[async_xml(child)]
+    timeout: u32,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "ordered_limits", ordered)]
+pub struct OrderedLimits {
+    #[async_xml(child)]
+    name: String,
+    #[async_xml(child, key = "tag")]
+    values: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "limits")]
+pub struct Limits {
+    #[async_xml(attribute)]
+    id: u32,
+    #[async_xml(child)]
+    name: String,
+    #[async_xml(child, key = "tag", deny_duplicate_keys)]
+    values: BTreeMap<String, u32>,
+}
+
+#[tokio::test]
+async fn test_struct_values() {
+    let xml = r#"<servers><server id="10"><host>a.example</host></server><server id="20"><host>b.example</host></server></servers>"#;
+    let de: Servers = from_str(xml).await.unwrap();
+    assert_eq!(
+        de.servers,
+        BTreeMap::from([
+            (
+                10,
+                Server {
+                    host: "a.example".into()
+                }
+            ),
+            (
+                20,
+                Server {
+                    host: "b.example".into()
+                }
+            ),
+        ])
+    );
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "servers")]
+pub struct Servers {
+    #[async_xml(child = "server", key = "@id")]
+    servers: BTreeMap<u32, Server>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "server")]
+pub struct Server {
+    #[async_xml(child)]
+    host: String,
+}
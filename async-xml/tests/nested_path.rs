@@ -0,0 +1,62 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_nested_path() {
+    let xml = r#"<order><header date="2022-08-01"><buyer ref="b-1"><id>42</id><name>Buyer</name></buyer><seller><id>7</id></seller></header><total>100</total></order>"#;
+    let de: Order = from_str(xml).await.unwrap();
+    let expected = Order {
+        date: "2022-08-01".into(),
+        buyer_id: 42,
+        buyer_ref: Some("b-1".into()),
+        seller_id: 7,
+        total: 100,
+        buyer_name: Some("Buyer".into()),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_nested_path_unknown_child() {
+    let xml = r#"<order><header date="2022-08-01"><buyer><id>42</id><name>Buyer</name></buyer><seller><id>7</id><name>Seller</name></seller></header><total>100</total></order>"#;
+    let err = from_str::<Order>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnexpectedChild(name) if name == "header/seller/name"));
+}
+
+#[tokio::test]
+async fn test_nested_path_missing() {
+    let xml = r#"<order><header date="2022-08-01"><seller><id>7</id></seller></header><total>100</total></order>"#;
+    let err = from_str::<Order>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::MissingChild(name) if name == "header/buyer/id"));
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "order")]
+pub struct Order {
+    #[async_xml(child = "header/@date")]
+    date: String,
+    #[async_xml(child = "header/buyer/id")]
+    buyer_id: u32,
+    #[async_xml(child = "header/buyer/@ref")]
+    buyer_ref: Option<String>,
+    #[async_xml(child = "header/seller/id")]
+    seller_id: u32,
+    #[async_xml(child)]
+    total: u32,
+    #[async_xml(child = "header/buyer/name")]
+    buyer_name: Option<String>,
+}
+
+#[tokio::test]
+async fn test_nested_path_skip_unknown() {
+    let xml = r#"<shipment><meta version="2"><carrier><code>DHL</code><note>fragile</note></carrier>text</meta><other/></shipment>"#;
+    let de: Shipment = from_str(xml).await.unwrap();
+    assert_eq!(de.carrier, "DHL");
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "shipment", allow_unknown)]
+pub struct Shipment {
+    #[async_xml(child = "meta/carrier/code")]
+    carrier: String,
+}
@@ -0,0 +1,79 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_optional_vec() {
+    let xml = r#"<order><note>a</note><note>b</note><items><item>1</item></items></order>"#;
+    let de: Order = from_str(xml).await.unwrap();
+    let expected = Order {
+        notes: Some(vec!["a".into(), "b".into()]),
+        items: Some(vec![1]),
+        lines: vec![],
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_optional_vec_absent() {
+    let xml = r#"<order><items/></order>"#;
+    let de: Order = from_str(xml).await.unwrap();
+    let expected = Order {
+        notes: None,
+        items: Some(vec![]),
+        lines: vec![],
+    };
+    assert_eq!(de, expected);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "order")]
+pub struct Order {
+    #[async_xml(child = "note")]
+    notes: Option<Vec<String>>,
+    #[async_xml(child, wrapped = "item")]
+    items: Option<Vec<u32>>,
+    #[async_xml(child = "line", max_occurs = 2)]
+    lines: Vec<u32>,
+}
+
+#[tokio::test]
+async fn test_max_occurs() {
+    let xml = r#"<order><line>1</line><line>2</line><line>3</line></order>"#;
+    let err = from_str::<Order>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnexpectedOccurrences(name, 3, 0, Some(2)) if name == "line"));
+}
+
+#[tokio::test]
+async fn test_min_occurs() {
+    let xml = r#"<route><stop>a</stop></route>"#;
+    let err = from_str::<Route>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnexpectedOccurrences(name, 1, 2, Some(10)) if name == "stop"));
+    assert_eq!(
+        from_str::<Route>(xml).await.unwrap_err().to_string(),
+        "Found 1 child elements <stop>, but expected between 2 and 10"
+    );
+}
+
+#[tokio::test]
+async fn test_min_occurs_satisfied() {
+    let xml = r#"<route><stop>a</stop><stop>b</stop></route>"#;
+    let de: Route = from_str(xml).await.unwrap();
+    assert_eq!(de.stops, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(de.extra, None);
+}
+
+#[tokio::test]
+async fn test_optional_min_occurs() {
+    let xml = r#"<route><stop>a</stop><stop>b</stop><extras></extras></route>"#;
+    let err = from_str::<Route>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnexpectedOccurrences(name, 0, 1, None) if name == "extra"));
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "route")]
+pub struct Route {
+    #[async_xml(child = "stop", min_occurs = 2, max_occurs = 10)]
+    stops: Vec<String>,
+    #[async_xml(child = "extras", wrapped = "extra", min_occurs = 1)]
+    extra: Option<Vec<String>>,
+}
@@ -0,0 +1,69 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_ordered() {
+    let xml = r#"<address id="1"><street>Main St</street><line>a</line><line>b</line><city>Springfield</city></address>"#;
+    let de: Address = from_str(xml).await.unwrap();
+    let expected = Address {
+        id: 1,
+        street: "Main St".into(),
+        lines: vec!["a".into(), "b".into()],
+        zip: None,
+        city: "Springfield".into(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_ordered_skipped_optional() {
+    let xml = r#"<address id="1"><street>Main St</street><zip>12345</zip><city>Springfield</city></address>"#;
+    let de: Address = from_str(xml).await.unwrap();
+    assert_eq!(de.zip, Some(12345));
+    assert!(de.lines.is_empty());
+}
+
+#[tokio::test]
+async fn test_out_of_order() {
+    let xml = r#"<address id="1"><city>Springfield</city><street>Main St</street></address>"#;
+    let err = from_str::<Address>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnexpectedOrder(name) if name == "street"));
+}
+
+#[tokio::test]
+async fn test_out_of_order_repeated() {
+    let xml = r#"<address id="1"><street>Main St</street><line>a</line><zip>12345</zip><line>b</line><city>Springfield</city></address>"#;
+    let err = from_str::<Address>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnexpectedOrder(name) if name == "line"));
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "address", ordered)]
+pub struct Address {
+    #[async_xml(attribute)]
+    id: u32,
+    #[async_xml(child)]
+    street: String,
+    #[async_xml(child = "line")]
+    lines: Vec<String>,
+    #[async_xml(child)]
+    zip: Option<u32>,
+    #[async_xml(child)]
+    city: String,
+}
+
+#[tokio::test]
+async fn test_unordered() {
+    let xml = r#"<point><y>2</y><x>1</x></point>"#;
+    let de: Point = from_str(xml).await.unwrap();
+    assert_eq!(de, Point { x: 1, y: 2 });
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "point")]
+pub struct Point {
+    #[async_xml(child)]
+    x: u32,
+    #[async_xml(child)]
+    y: u32,
+}
@@ -0,0 +1,88 @@
+use async_xml::{from_str, util::XmlAttribute, util::XmlNode};
+use async_xml_derive::FromXml;
+use std::collections::{BTreeMap, HashMap};
+
+#[tokio::test]
+async fn test_other_attributes_map() {
+    let xml = r#"<product id="1" vendor:color="red" vendor:size="XL">Shirt</product>"#;
+    let de: Product = from_str(xml).await.unwrap();
+    let mut other = HashMap::new();
+    other.insert("vendor:color".to_string(), "red".to_string());
+    other.insert("vendor:size".to_string(), "XL".to_string());
+    let expected = Product {
+        id: 1,
+        name: "Shirt".into(),
+        other,
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_other_attributes_reserved() {
+    let xml = r#"<product id="1" xmlns="urn:example" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="urn:example product.xsd" lang="en">Shirt</product>"#;
+    let de: Product = from_str(xml).await.unwrap();
+    let mut other = HashMap::new();
+    other.insert("lang".to_string(), "en".to_string());
+    assert_eq!(de.other, other);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "product")]
+pub struct Product {
+    #[async_xml(attribute)]
+    id: u32,
+    #[async_xml(value)]
+    name: String,
+    #[async_xml(other_attributes)]
+    other: HashMap<String, String>,
+}
+
+#[tokio::test]
+async fn test_other_attributes_vec() {
+    let xml = r#"<link href="https://example.com" rel="self" data-x="1"/>"#;
+    let de: Link = from_str(xml).await.unwrap();
+    let expected = Link {
+        href: "https://example.com".into(),
+        other: vec![
+            XmlAttribute {
+                name: "rel".into(),
+                value: "self".into(),
+            },
+            XmlAttribute {
+                name: "data-x".into(),
+                value: "1".into(),
+            },
+        ],
+    };
+    assert_eq!(de, expected);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "link")]
+pub struct Link {
+    #[async_xml(attribute)]
+    href: String,
+    #[async_xml(other_attributes)]
+    other: Vec<XmlAttribute>,
+}
+
+#[tokio::test]
+async fn test_other_attributes_before_remains() {
+    let xml = r#"<entry a="1" b="2"><child/></entry>"#;
+    let de: Entry = from_str(xml).await.unwrap();
+    assert_eq!(
+        de.other,
+        BTreeMap::from([("a".into(), "1".into()), ("b".into(), "2".into())])
+    );
+    assert!(de.remains.attributes.is_empty());
+    assert_eq!(de.remains.children.len(), 1);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "entry")]
+pub struct Entry {
+    #[async_xml(remains)]
+    remains: XmlNode,
+    #[async_xml(other_attributes)]
+    other: BTreeMap<String, String>,
+}
@@ -0,0 +1,107 @@
+use async_xml::push::push_parser;
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_top_level() {
+    let (sender, mut parser) = push_parser();
+    sender.push(r#"<item id="1">a</it"#).unwrap();
+    sender.push(r#"em><item id="2">"#).unwrap();
+
+    // first item is complete, so this must not wait for more input
+    let de: Option<Item> = parser.next().await.unwrap();
+    assert_eq!(
+        de,
+        Some(Item {
+            id: 1,
+            text: "a".into()
+        })
+    );
+
+    sender.push("b</item>").unwrap();
+    sender.finish();
+    let de: Option<Item> = parser.next().await.unwrap();
+    assert_eq!(
+        de,
+        Some(Item {
+            id: 2,
+            text: "b".into()
+        })
+    );
+    let de: Option<Item> = parser.next().await.unwrap();
+    assert_eq!(de, None);
+}
+
+#[tokio::test]
+async fn test_children() {
+    let xml = r#"<?xml version="1.0"?><feed><item id="1">ä</item><item id="2">b</item></feed>"#;
+    let (sender, mut parser) = push_parser();
+    let feeder = tokio::spawn(async move {
+        // split into tiny chunks, including in the middle of multi-byte characters
+        for chunk in xml.as_bytes().chunks(3) {
+            sender.push(chunk.to_vec()).unwrap();
+            tokio::task::yield_now().await;
+        }
+    });
+
+    let mut items = Vec::new();
+    while let Some(item) = parser.next_child::<Item>().await.unwrap() {
+        items.push(item);
+    }
+    feeder.await.unwrap();
+    assert_eq!(
+        items,
+        vec![
+            Item {
+                id: 1,
+                text: "ä".into()
+            },
+            Item {
+                id: 2,
+                text: "b".into()
+            },
+        ]
+    );
+    assert_eq!(parser.next_child::<Item>().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_children_root_namespaces() {
+    let (sender, mut parser) = push_parser();
+    sender
+        .push(r#"<feed xmlns:i="http://www.w3.org/2001/XMLSchema-instance"><item id="1" i:nil="false">a</item></feed>"#)
+        .unwrap();
+    sender.finish();
+    let item = parser.next_child::<Item>().await.unwrap();
+    assert_eq!(
+        item,
+        Some(Item {
+            id: 1,
+            text: "a".into()
+        })
+    );
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_children_truncated() {
+    let (sender, mut parser) = push_parser();
+    sender.push(r#"<feed><item id="1">a</item>"#).unwrap();
+    sender.finish();
+    while parser.next_child::<Item>().await.unwrap().is_some() {}
+}
+
+#[tokio::test]
+async fn test_closed() {
+    let (sender, parser) = push_parser();
+    drop(parser);
+    assert!(sender.push("<item />").is_err());
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "item")]
+pub struct Item {
+    #[async_xml(attribute)]
+    id: u32,
+    #[async_xml(value)]
+    text: String,
+}
@@ -0,0 +1,75 @@
+use async_xml::{Error, PeekingReader, Registry};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_any() {
+    let mut registry = Registry::new();
+    registry.register::<Order>().register::<Cancel>();
+
+    let mut reader = PeekingReader::from_str(r#"<order id="1"><item>a</item></order>"#);
+    let de = registry.deserialize(&mut reader).await.unwrap();
+    let expected = Order {
+        id: "1".into(),
+        item: "a".into(),
+    };
+    assert_eq!(de.downcast_ref::<Order>(), Some(&expected));
+
+    let mut reader = PeekingReader::from_str(r#"<cancel id="2" />"#);
+    let de = registry.deserialize(&mut reader).await.unwrap();
+    let expected = Cancel { id: "2".into() };
+    assert_eq!(de.downcast_ref::<Cancel>(), Some(&expected));
+}
+
+#[tokio::test]
+async fn test_handler() {
+    let mut registry = Registry::new();
+    registry
+        .register_handler(|o: Order| async move { Ok(format!("order {}", o.id)) })
+        .register_with(|c: Cancel| format!("cancel {}", c.id));
+
+    let mut reader = PeekingReader::from_str(r#"<order id="1"><item>a</item></order>"#);
+    let de = registry.deserialize(&mut reader).await.unwrap();
+    assert_eq!(de, "order 1");
+
+    let mut reader = PeekingReader::from_str(r#"<cancel id="2" />"#);
+    let de = registry.deserialize(&mut reader).await.unwrap();
+    assert_eq!(de, "cancel 2");
+}
+
+#[tokio::test]
+async fn test_unregistered() {
+    let mut registry = Registry::new();
+    registry.register::<Order>();
+    assert!(registry.contains("order"));
+    assert!(!registry.contains("cancel"));
+
+    let mut reader = PeekingReader::from_str(r#"<cancel id="2" />"#);
+    let de = registry.deserialize(&mut reader).await;
+    assert!(matches!(de, Err(Error::UnregisteredElement(name)) if name == "cancel"));
+}
+
+#[test]
+#[should_panic]
+fn test_register_unnamed() {
+    let mut registry = Registry::<&[u8]>::new();
+    registry.register::<Unnamed>();
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "order")]
+pub struct Order {
+    #[async_xml(attribute)]
+    id: String,
+    #[async_xml(child)]
+    item: String,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "cancel")]
+pub struct Cancel {
+    #[async_xml(attribute)]
+    id: String,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+pub struct Unnamed {}
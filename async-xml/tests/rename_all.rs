@@ -0,0 +1,120 @@
+use async_xml::from_str;
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_pascal_case() {
+    let xml = r#"<Order OrderNumber="17"><CustomerName>Bob</CustomerName><ShippingAddress>Main St</ShippingAddress></Order>"#;
+    let de: Order = from_str(xml).await.unwrap();
+    let expected = Order {
+        order_number: 17,
+        customer_name: "Bob".into(),
+        address: "Main St".into(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_separate_attributes() {
+    let xml = r#"<config max-retries="3" LOG_LEVEL="debug"><retryDelay>5</retryDelay></config>"#;
+    let de: Config = from_str(xml).await.unwrap();
+    let expected = Config {
+        max_retries: 3,
+        log_level: "debug".into(),
+        retry_delay: 5,
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_variants() {
+    let xml = r#"<payment><credit-card number="1234" /></payment>"#;
+    let de: Payment = from_str(xml).await.unwrap();
+    assert_eq!(de, Payment::CreditCard { number: 1234 });
+
+    let xml =
+        r#"<payment><bank-transfer><account-holder>Bob</account-holder></bank-transfer></payment>"#;
+    let de: Payment = from_str(xml).await.unwrap();
+    let expected = Payment::BankTransfer {
+        account_holder: "Bob".into(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_unit_variants() {
+    let xml = r#"<status>IN_TRANSIT</status>"#;
+    let de: Status = from_str(xml).await.unwrap();
+    assert_eq!(de, Status::InTransit);
+}
+
+#[tokio::test]
+async fn test_non_ascii() {
+    let xml = r#"<lesson><übungNr>3</übungNr></lesson>"#;
+    let de: Lesson = from_str(xml).await.unwrap();
+    assert_eq!(de, Lesson { übung_nr: 3 });
+
+    let xml = r#"<level>ärger</level>"#;
+    let de: Level = from_str(xml).await.unwrap();
+    assert_eq!(de, Level::Ärger);
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "lesson", rename_all = "camelCase")]
+pub struct Lesson {
+    #[async_xml(child)]
+    übung_nr: u32,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "level", text, rename_all = "camelCase")]
+pub enum Level {
+    Ärger,
+    Ruhe,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "Order", rename_all = "PascalCase")]
+pub struct Order {
+    #[async_xml(attribute)]
+    order_number: u32,
+    #[async_xml(child)]
+    customer_name: String,
+    #[async_xml(child, rename = "ShippingAddress")]
+    address: String,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(
+    rename = "config",
+    rename_all = "camelCase",
+    rename_all_attributes = "kebab-case"
+)]
+pub struct Config {
+    #[async_xml(attribute)]
+    max_retries: u32,
+    #[async_xml(attribute, rename = "LOG_LEVEL")]
+    log_level: String,
+    #[async_xml(child)]
+    retry_delay: u32,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "payment", rename_all = "kebab-case")]
+pub enum Payment {
+    CreditCard {
+        #[async_xml(attribute)]
+        number: u32,
+    },
+    BankTransfer {
+        #[async_xml(child)]
+        account_holder: String,
+    },
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "status", text, rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    Pending,
+    InTransit,
+    Delivered,
+}
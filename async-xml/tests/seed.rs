@@ -0,0 +1,124 @@
+use async_xml::{reader::DeserializeSeed, Error, PeekingReader, Visitor};
+use async_xml_derive::FromXml;
+use tokio::io::AsyncBufRead;
+
+#[tokio::test]
+async fn test_with_visitor() {
+    let xml = r#"<order>42</order>"#;
+    let mut reader = PeekingReader::from_str(xml);
+    let de = reader
+        .deserialize_with_visitor(RootVisitor::new("order"))
+        .await
+        .unwrap();
+    assert_eq!(de, "42");
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_with_visitor_wrong_root() {
+    let xml = r#"<invoice>42</invoice>"#;
+    let mut reader = PeekingReader::from_str(xml);
+    reader
+        .deserialize_with_visitor(RootVisitor::new("order"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_seed() {
+    let xml = r#"<order>42</order>"#;
+    let mut reader = PeekingReader::from_str(xml);
+    let de = reader.deserialize_seed(RootSeed("order")).await.unwrap();
+    assert_eq!(de, "42");
+}
+
+#[tokio::test]
+async fn test_seed_child() {
+    let xml = r#"<report><entry>a</entry><entry>b</entry><single>c</single></report>"#;
+    let de: Report = async_xml::from_str(xml).await.unwrap();
+    let expected = Report {
+        entries: vec!["a".into(), "b".into()],
+        single: "c".into(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn test_seed_child_wrong() {
+    let xml = r#"<report><single>a</single></report>"#;
+    let _: ReportWrong = async_xml::from_str(xml).await.unwrap();
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "report")]
+pub struct Report {
+    #[async_xml(child, rename = "entry", seed = "entry_seed")]
+    entries: Vec<String>,
+    #[async_xml(child, seed = "single_seed")]
+    single: String,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "report")]
+pub struct ReportWrong {
+    #[async_xml(child, seed = "entry_seed")]
+    single: String,
+}
+
+fn entry_seed() -> RootSeed {
+    RootSeed("entry")
+}
+
+fn single_seed() -> RootSeed {
+    RootSeed("single")
+}
+
+/// Reads the text of an element whose name is only known at runtime
+pub struct RootVisitor {
+    expected: &'static str,
+    text: Option<String>,
+}
+
+impl RootVisitor {
+    fn new(expected: &'static str) -> Self {
+        Self {
+            expected,
+            text: None,
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl<B: AsyncBufRead + Unpin> Visitor<B> for RootVisitor {
+    type Output = String;
+
+    fn visit_tag(&mut self, name: &str) -> Result<(), Error> {
+        if name != self.expected {
+            return Err(Error::WrongStart(self.expected.into(), name.into()));
+        }
+        Ok(())
+    }
+
+    fn visit_text(&mut self, text: &str) -> Result<(), Error> {
+        if self.text.replace(text.into()).is_some() {
+            return Err(Error::DoubleText);
+        }
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Error> {
+        self.text.ok_or(Error::MissingText)
+    }
+}
+
+pub struct RootSeed(&'static str);
+
+impl<B: AsyncBufRead + Unpin> DeserializeSeed<B> for RootSeed {
+    type Value = String;
+    type Visitor = RootVisitor;
+
+    fn into_visitor(self) -> Self::Visitor {
+        RootVisitor::new(self.0)
+    }
+}
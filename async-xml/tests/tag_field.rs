@@ -0,0 +1,95 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_tag_string() {
+    let xml = r#"<shape><circle size="2"/><square size="3"/></shape>"#;
+    let de: Shape = from_str(xml).await.unwrap();
+    let expected = Shape {
+        parts: vec![
+            Part {
+                name: "circle".into(),
+                kind: Some(Kind::Circle),
+                size: 2,
+            },
+            Part {
+                name: "square".into(),
+                kind: Some(Kind::Square),
+                size: 3,
+            },
+        ],
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_tag_unknown_variant() {
+    let xml = r#"<shape><triangle size="2"/></shape>"#;
+    let err = from_str::<Shape>(xml).await.unwrap_err();
+    match err {
+        Error::InnerDeserialiaztionError(name, e) => {
+            assert_eq!(name, "part");
+            assert!(matches!(*e, Error::UnknownVariant(variant) if variant == "triangle"));
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_tag_optional_child() {
+    let xml = r#"<drawing><b size="1"/></drawing>"#;
+    let de: Drawing = from_str(xml).await.unwrap();
+    let expected = Drawing {
+        part: Some(Tagged {
+            name: "b".into(),
+            size: 1,
+        }),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_tag_optional_child_absent() {
+    let xml = r#"<drawing></drawing>"#;
+    let de: Drawing = from_str(xml).await.unwrap();
+    assert_eq!(de, Drawing { part: None });
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "drawing")]
+pub struct Drawing {
+    #[async_xml(child = "b")]
+    part: Option<Tagged>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+pub struct Tagged {
+    #[async_xml(tag)]
+    name: String,
+    #[async_xml(attribute)]
+    size: u32,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "shape")]
+pub struct Shape {
+    #[async_xml(child = "part", alias = "circle", alias = "square", alias = "triangle")]
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+pub struct Part {
+    #[async_xml(tag)]
+    name: String,
+    #[async_xml(tag)]
+    kind: Option<Kind>,
+    #[async_xml(attribute)]
+    size: u32,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(text, rename_all = "lowercase")]
+pub enum Kind {
+    Circle,
+    Square,
+}
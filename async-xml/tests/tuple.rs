@@ -0,0 +1,64 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+
+#[tokio::test]
+async fn test_positional() {
+    let xml = r#"<coord><v>1</v><v>2</v></coord>"#;
+    let de: Coord = from_str(xml).await.unwrap();
+    assert_eq!(de, Coord(1, 2));
+}
+
+#[tokio::test]
+async fn test_positional_any_name() {
+    let xml = r#"<coord><x>3</x><y>4</y></coord>"#;
+    let de: Coord = from_str(xml).await.unwrap();
+    assert_eq!(de, Coord(3, 4));
+}
+
+#[tokio::test]
+async fn test_positional_missing() {
+    let xml = r#"<coord><v>1</v></coord>"#;
+    let err = from_str::<Coord>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::MissingChild(name) if name == "child #2"));
+}
+
+#[tokio::test]
+async fn test_positional_invalid() {
+    let xml = r#"<coord><x>1</x><y>two</y></coord>"#;
+    let err = from_str::<Coord>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::InnerDeserialiaztionError(name, _) if name == "y"));
+}
+
+#[tokio::test]
+async fn test_positional_too_many() {
+    let xml = r#"<coord><v>1</v><v>2</v><v>3</v></coord>"#;
+    let err = from_str::<Coord>(xml).await.unwrap_err();
+    assert!(matches!(err, Error::UnexpectedChild(name) if name == "v"));
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "coord")]
+pub struct Coord(#[async_xml(child)] i32, #[async_xml(child)] i32);
+
+#[tokio::test]
+async fn test_mixed() {
+    let xml = r#"<range unit="m"><label>len</label><from>1</from><to>5</to></range>"#;
+    let de: Range = from_str(xml).await.unwrap();
+    assert_eq!(de, Range("m".into(), Some(1), "len".into(), 5));
+}
+
+#[tokio::test]
+async fn test_mixed_optional_end() {
+    let xml = r#"<range unit="m"><from>1</from><label>len</label></range>"#;
+    let de: Range = from_str(xml).await.unwrap();
+    assert_eq!(de, Range("m".into(), Some(1), "len".into(), 0));
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "range")]
+pub struct Range(
+    #[async_xml(attribute, rename = "unit")] String,
+    #[async_xml(child)] Option<u32>,
+    #[async_xml(child, rename = "label")] String,
+    #[async_xml(child, default)] u32,
+);
@@ -0,0 +1,54 @@
+use async_xml::{from_str, Error};
+use async_xml_derive::FromXml;
+use std::collections::BTreeSet;
+
+#[tokio::test]
+async fn test_wrapped() {
+    let xml = r#"<order><items><item id="1"/><item id="2"/></items><tags><tag>b</tag><tag>a</tag></tags></order>"#;
+    let de: Order = from_str(xml).await.unwrap();
+    let expected = Order {
+        items: vec![Item { id: 1 }, Item { id: 2 }],
+        tags: BTreeSet::from(["a".into(), "b".into()]),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_wrapped_empty() {
+    let xml = r#"<order><items/></order>"#;
+    let de: Order = from_str(xml).await.unwrap();
+    let expected = Order {
+        items: Vec::new(),
+        tags: BTreeSet::new(),
+    };
+    assert_eq!(de, expected);
+}
+
+#[tokio::test]
+async fn test_wrapped_unexpected_child() {
+    let xml = r#"<order><items><item id="1"/><tag>a</tag></items></order>"#;
+    let err = from_str::<Order>(xml).await.unwrap_err();
+    match err {
+        Error::InnerDeserialiaztionError(name, e) => {
+            assert_eq!(name, "items");
+            assert!(matches!(*e, Error::UnexpectedChild(child) if child == "tag"));
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "order")]
+pub struct Order {
+    #[async_xml(child = "items", wrapped = "item")]
+    items: Vec<Item>,
+    #[async_xml(child, wrapped = "tag")]
+    tags: BTreeSet<String>,
+}
+
+#[derive(Debug, PartialEq, FromXml)]
+#[async_xml(rename = "item")]
+pub struct Item {
+    #[async_xml(attribute)]
+    id: u32,
+}